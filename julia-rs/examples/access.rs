@@ -0,0 +1,45 @@
+use std::convert::TryFrom;
+
+use julia::api::{Array, Datatype, Exception, JlValue, Julia, Value};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let xs = Array::from_value(jl.eval_string("[1.0, 2.0, 3.0]").unwrap()).unwrap();
+    xs.at(0).set(&Value::from(10.0)).unwrap();
+    assert_eq!(f64::try_from(&xs.at(0).get().unwrap()).unwrap(), 10.0);
+
+    match xs.at(3).get() {
+        Err(Error::BoundsError(msg)) => println!("{}", msg),
+        other => panic!("expected BoundsError, got {:?}", other),
+    }
+
+    // [1 2; 3 4]
+    let m = Array::from_value(jl.eval_string("[1 2; 3 4]").unwrap()).unwrap();
+    assert_eq!(i64::try_from(&m.at2(1, 0).get().unwrap()).unwrap(), 3);
+    m.at2(0, 1).set(&Value::from(20i64)).unwrap();
+    assert_eq!(i64::try_from(&m.at2(0, 1).get().unwrap()).unwrap(), 20);
+    assert_eq!(m.dims().unwrap(), [2, 2]);
+
+    // Array{Float64, 0} holds a single element and has no dimensions
+    let scalar = Array::alloc_0d(&Datatype::float64()).unwrap();
+    assert_eq!(scalar.ndims().unwrap(), 0);
+    assert!(scalar.dims().unwrap().is_empty());
+    assert_eq!(scalar.len().unwrap(), 1);
+    scalar.at0().set(&Value::from(1.5)).unwrap();
+    assert_eq!(f64::try_from(&scalar.at0().get().unwrap()).unwrap(), 1.5);
+    assert!(xs.at0().get().is_err());
+
+    // x .= src
+    m.broadcast_assign(&Value::from(7i64)).unwrap();
+    assert_eq!(i64::try_from(&m.at2(1, 1).get().unwrap()).unwrap(), 7);
+    let row = jl.eval_string("[1 2]").unwrap();
+    m.broadcast_assign(&row).unwrap();
+    assert_eq!(i64::try_from(&m.at2(1, 1).get().unwrap()).unwrap(), 2);
+    let wrong = jl.eval_string("[1, 2, 3]").unwrap();
+    match m.broadcast_assign(&wrong) {
+        Err(Error::UnhandledException(Exception::DimensionMismatch(_))) => (),
+        other => panic!("expected DimensionMismatch, got {:?}", other),
+    }
+}
@@ -0,0 +1,19 @@
+use std::convert::TryFrom;
+
+use julia::api::Julia;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let task = jl.eval_async("sleep(0.1); 6 * 7").unwrap();
+    // the task only runs once we yield, so it can't be done yet
+    assert!(!task.is_done().unwrap());
+
+    let answer = i64::try_from(&task.fetch().unwrap()).unwrap();
+    assert!(task.is_done().unwrap());
+    assert_eq!(answer, 42);
+    println!("answer = {}", answer);
+
+    let failing = jl.eval_async("error(\"oops\")").unwrap();
+    assert!(failing.fetch().is_err());
+}
@@ -0,0 +1,64 @@
+use std::convert::TryFrom;
+
+use julia::api::{Datatype, JlValue, Julia};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let x = Datatype::int64()
+        .new_bits(42i64.to_ne_bytes().to_vec())
+        .unwrap();
+    assert_eq!(i64::try_from(&x).unwrap(), 42);
+
+    let y = Datatype::uint8().new_bits(vec![7]).unwrap();
+    assert_eq!(u8::try_from(&y).unwrap(), 7);
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Point {
+        x: f64,
+        y: i32,
+    }
+    jl.eval_string("struct Point; x::Float64; y::Int32; end")
+        .unwrap();
+    let point = Datatype::from_value(jl.eval_string("Point").unwrap()).unwrap();
+    let p = point.box_bits(Point { x: 1.5, y: -3 }).unwrap();
+    assert_eq!(f64::try_from(&p.get("x").unwrap()).unwrap(), 1.5);
+    assert_eq!(i32::try_from(&p.get("y").unwrap()).unwrap(), -3);
+    let z = Datatype::float64().box_bits(2.5f64).unwrap();
+    assert_eq!(f64::try_from(&z).unwrap(), 2.5);
+    // same size, but u32 is less aligned than Float64
+    match Datatype::float64().box_bits([0u32; 2]) {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+
+    // too few and too many bytes
+    match Datatype::int64().new_bits(vec![0; 4]) {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+    match Datatype::int32().new_bits(vec![0; 8]) {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+
+    // not a bits type
+    let string = Datatype::from_value(jl.eval_string("String").unwrap()).unwrap();
+    match string.new_bits(vec![0; 8]) {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+    jl.eval_string("struct Pair; a::Int8; b::Float64; c::String; end")
+        .unwrap();
+    let pair = Datatype::from_value(jl.eval_string("Pair").unwrap()).unwrap();
+    assert_eq!(pair.field_offset(0).unwrap(), 0);
+    assert_eq!(pair.field_offset(1).unwrap(), 8);
+    assert!(!pair.field_is_pointer(1).unwrap());
+    assert!(pair.field_is_pointer(2).unwrap());
+    assert!(pair.field_offset(3).is_err());
+    assert!(Datatype::number().field_offset(0).is_err());
+
+    println!("ok");
+}
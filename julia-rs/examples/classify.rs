@@ -0,0 +1,80 @@
+use std::convert::TryFrom;
+
+use julia::api::{Datatype, Julia, Value};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    // the exceptions' fields, for handling them programmatically
+    let ex = match jl.eval_string("[1, 2, 3][4]") {
+        Err(Error::UnhandledException(ex)) => ex,
+        other => panic!("expected an exception, got {:?}", other),
+    };
+    let bounds = ex.as_bounds().unwrap();
+    assert_eq!(bounds.index.unwrap().to_string(), "(4,)");
+    assert!(ex.as_method().is_none());
+    let ex = match jl.eval_string("1 + \"a\"") {
+        Err(Error::UnhandledException(ex)) => ex,
+        other => panic!("expected an exception, got {:?}", other),
+    };
+    let method = ex.as_method().unwrap();
+    assert_eq!(method.function.to_string(), "+");
+    let arg_types: Vec<String> = method.arg_types.iter().map(|t| t.to_string()).collect();
+    assert_eq!(arg_types, ["Int64", "String"]);
+    let ex = match jl.eval_string("1::String") {
+        Err(Error::UnhandledException(ex)) => ex,
+        other => panic!("expected an exception, got {:?}", other),
+    };
+    let type_error = ex.as_type().unwrap();
+    assert_eq!(type_error.expected.to_string(), "String");
+    assert_eq!(i64::try_from(&type_error.got).unwrap(), 1);
+
+    match jl.eval_string("[1, 2, 3][4]").map_err(Error::classify) {
+        Err(Error::BoundsError(msg)) => println!("bounds: {}", msg),
+        other => panic!("expected BoundsError, got {:?}", other),
+    }
+
+    match jl.eval_string("1 + \"a\"").map_err(Error::classify) {
+        Err(Error::MethodError(msg)) => println!("method: {}", msg),
+        other => panic!("expected MethodError, got {:?}", other),
+    }
+
+    match jl.eval_string("1::String").map_err(Error::classify) {
+        Err(Error::TypeError(msg)) => println!("type: {}", msg),
+        other => panic!("expected TypeError, got {:?}", other),
+    }
+
+    let x = Value::from(3i64).typeassert(&Datatype::int64()).unwrap();
+    assert_eq!(i64::try_from(&x).unwrap(), 3);
+    assert!(x.typeassert(&Datatype::number()).is_ok());
+    match x.typeassert(&Datatype::float64()) {
+        Err(Error::TypeError(msg)) => println!("typeassert: {}", msg),
+        other => panic!("expected TypeError, got {:?}", other),
+    }
+
+    let err = jl.eval_string("sqrt(-1.0)").unwrap_err();
+    assert!(err.user_message().starts_with("DomainError"));
+    println!("{}", err.user_message());
+    assert_eq!(Error::Timeout.user_message(), "Timeout");
+
+    // syntax errors point at the offending part of the source
+    match jl.eval_string("x = (1 + ") {
+        Err(Error::ParseError(msg)) => {
+            assert!(msg.contains("x = (1 + "));
+            println!("parse: {}", msg);
+        }
+        other => panic!("expected ParseError, got {:?}", other),
+    }
+
+    // anything else stays an UnhandledException
+    match jl.eval_string("error(\"oops\")").map_err(Error::classify) {
+        Err(Error::UnhandledException(ex)) => {
+            // Display shows Julia's own message
+            assert_eq!(ex.to_string(), "oops");
+            let err: Box<dyn std::error::Error> = Box::new(ex);
+            println!("unhandled: {}", err);
+        }
+        other => panic!("expected UnhandledException, got {:?}", other),
+    }
+}
@@ -0,0 +1,25 @@
+use std::convert::TryFrom;
+use std::fs;
+
+use julia::api::{Array, JlValue, Julia};
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let depot = std::env::temp_dir().join("julia-rs-depot-example");
+    jl.set_depot_path(std::slice::from_ref(&depot)).unwrap();
+    assert!(depot.is_dir());
+
+    let collect = jl.eval_string("collect(Any, DEPOT_PATH)").unwrap();
+    let paths: Vec<String> = Array::from_value(collect)
+        .unwrap()
+        .as_vec()
+        .unwrap()
+        .iter()
+        .map(|p| String::try_from(p).unwrap())
+        .collect();
+    assert_eq!(paths, [depot.to_string_lossy().into_owned()]);
+    println!("DEPOT_PATH = {:?}", paths);
+
+    fs::remove_dir_all(&depot).unwrap();
+}
@@ -0,0 +1,43 @@
+use std::convert::TryFrom;
+
+use julia::api::Julia;
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let diag = jl
+        .eval_with_diagnostics("@warn \"x is deprecated\"; println(stderr, \"careful\"); 1 + 2")
+        .unwrap();
+    assert_eq!(i64::try_from(&diag.value).unwrap(), 3);
+    for warning in &diag.warnings {
+        println!("{}", warning);
+    }
+    // plain stderr output isn't a diagnostic
+    assert_eq!(diag.warnings, ["Warn: x is deprecated"]);
+    assert_eq!(diag.stderr, "careful\n");
+
+    // info messages aren't diagnostics
+    let diag = jl.eval_with_diagnostics("@info \"hello\"").unwrap();
+    assert!(diag.warnings.is_empty());
+
+    let cell = jl
+        .exec_cell("println(\"out\"); println(stderr, \"err\"); @warn \"w\"; 40 + 2")
+        .unwrap();
+    assert_eq!(i64::try_from(&cell.value).unwrap(), 42);
+    assert_eq!(cell.stdout, "out\n");
+    assert_eq!(cell.stderr, "err\n");
+    assert_eq!(cell.warnings, ["Warn: w"]);
+
+    // stdout is restored after a failing cell
+    assert!(jl.exec_cell("print(\"lost\"); error(\"boom\")").is_err());
+    let diag = jl
+        .eval_with_diagnostics("println(\"stdout is back\")")
+        .unwrap();
+    assert!(diag.warnings.is_empty());
+
+    match jl.eval_with_diagnostics("1 +") {
+        Err(Error::ParseError(msg)) => println!("{}", msg),
+        other => panic!("expected ParseError, got {:?}", other),
+    }
+}
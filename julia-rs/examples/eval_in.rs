@@ -0,0 +1,102 @@
+use std::convert::TryFrom;
+
+use julia::api::{Exception, JlValue, Julia, Module};
+use julia::error::Error;
+use julia::jl_eval;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let m = Module::from_value(jl.eval_string("module Scratch end").unwrap()).unwrap();
+    jl.eval_in(&m, "x = 1; double(y) = 2y").unwrap();
+
+    let x = jl.eval_in(&m, "double(x) + 1").unwrap();
+    assert_eq!(i64::try_from(&x).unwrap(), 3);
+
+    // x and double are only bound in Scratch
+    assert!(jl.eval_string("double(x)").is_err());
+    // parse errors are returned too
+    assert!(jl.eval_in(&m, "1 +").is_err());
+
+    let (a, b) = (2, 5);
+    let sum = jl_eval!(jl, "s = {} + {}", a, b).unwrap();
+    assert_eq!(i64::try_from(&sum).unwrap(), 7);
+    let name = "s";
+    let s = jl_eval!(jl, "{name} * 2").unwrap();
+    assert_eq!(i64::try_from(&s).unwrap(), 14);
+
+    // multiple top-level statements, the last one's value is returned
+    let z = jl.eval_script("z = 20\nz + 1\n").unwrap();
+    assert_eq!(i64::try_from(&z).unwrap(), 21);
+    let z = jl.eval_string("z").unwrap();
+    assert_eq!(i64::try_from(&z).unwrap(), 20);
+    assert!(jl.eval_script("w = 1\nw +\n").is_err());
+
+    let script = "loaded = 1\nloaded += 1\n";
+    let ex = jl
+        .parse_file(&mut script.as_bytes(), Some("script.jl"))
+        .unwrap();
+    assert_eq!(ex.typename().unwrap(), "Expr");
+    // parsing doesn't define anything
+    assert!(jl.eval_string("loaded").is_err());
+    let loaded = jl
+        .load_file(&mut script.as_bytes(), Some("script.jl"))
+        .unwrap();
+    assert_eq!(i64::try_from(&loaded).unwrap(), 2);
+
+    // fresh modules don't see Main's bindings
+    let sandbox = jl.new_module("Sandbox", true).unwrap();
+    assert!(jl.eval_in(&sandbox, "z").is_err());
+    let n = jl.eval_in(&sandbox, "z = length([1, 2, 3])").unwrap();
+    assert_eq!(i64::try_from(&n).unwrap(), 3);
+    assert_eq!(i64::try_from(&jl.eval_string("z").unwrap()).unwrap(), 20);
+    let bare = jl.new_module("Bare", false).unwrap();
+    assert!(jl.eval_in(&bare, "length([1, 2, 3])").is_err());
+
+    // generated code, loaded into the sandbox with its own file name
+    let generated = "f(x) = x + 1\ng(x) = f(x) * 2\ng(20)\n";
+    let g = sandbox.include_string(generated, "generated.jl").unwrap();
+    assert_eq!(i64::try_from(&g).unwrap(), 42);
+    assert!(jl.eval_string("g(1)").is_err());
+    match sandbox.include_string("a = 1\nb = a +\n", "broken.jl") {
+        Err(Error::UnhandledException(Exception::Load(ex))) => {
+            let file = String::try_from(&ex.get("file").unwrap()).unwrap();
+            assert_eq!(file, "broken.jl");
+        }
+        other => panic!("expected LoadError, got {:?}", other),
+    }
+
+    // Bare doesn't import Base, but the lookup falls through to it
+    jl.eval_string("helper() = 42").unwrap();
+    let nested = jl.new_module("Nested", false).unwrap();
+    assert!(bare.function("length").is_err());
+    assert!(bare.function_recursive("length").is_ok());
+    jl.eval_in(&bare, "same(x) = x").unwrap();
+    assert!(bare.function_recursive("same").is_ok());
+    // like in Julia code, names of the parent aren't visible
+    match nested.function_recursive("helper") {
+        Err(Error::UndefVar(_)) => (),
+        other => panic!("expected UndefVar, got {:?}", other),
+    }
+    let user = jl
+        .eval_string("module Lib; export twice; twice(x) = 2x; end; module User; using ..Lib; end")
+        .unwrap();
+    let user = Module::from_value(user).unwrap();
+    assert!(user.function_recursive("twice").is_ok());
+    match bare.function_recursive("pi") {
+        Err(Error::NotAFunction) => (),
+        other => panic!("expected NotAFunction, got {:?}", other),
+    }
+    match bare.function_recursive("no_such_function") {
+        Err(Error::UndefVar(_)) => (),
+        other => panic!("expected UndefVar, got {:?}", other),
+    }
+
+    assert_eq!(m.fullname().unwrap(), "Main.Scratch");
+    let main = m.parent().unwrap();
+    assert_eq!(main.fullname().unwrap(), "Main");
+    assert_eq!(main.parent().unwrap().fullname().unwrap(), "Main");
+    let math = Module::from_value(jl.eval_string("Base.Math").unwrap()).unwrap();
+    assert_eq!(math.fullname().unwrap(), "Base.Math");
+    println!("ok");
+}
@@ -0,0 +1,52 @@
+use julia::api::{FromJulia, JlValue, Julia, Value};
+use julia::error::Result;
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+    tags: Vec<String>,
+}
+
+impl FromJulia for Point {
+    fn from_julia(value: &Value) -> Result<Self> {
+        Ok(Point {
+            x: value.get("x")?.extract()?,
+            y: value.get("y")?.extract()?,
+            tags: value.get("tags")?.extract()?,
+        })
+    }
+}
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let xs: Vec<i64> = jl.eval_string("[1, 2, 3]").unwrap().extract().unwrap();
+    assert_eq!(xs, [1, 2, 3]);
+    let nested: Vec<Vec<f64>> = jl
+        .eval_string("[[1.0], [2.0, 3.0]]")
+        .unwrap()
+        .extract()
+        .unwrap();
+    assert_eq!(nested, [vec![1.0], vec![2.0, 3.0]]);
+    assert!(jl
+        .eval_string("[1, 2]")
+        .unwrap()
+        .extract::<Vec<String>>()
+        .is_err());
+    assert!(jl.eval_string("1").unwrap().extract::<Vec<i64>>().is_err());
+
+    jl.eval_string("struct Point; x::Float64; y::Float64; tags::Vector{String}; end")
+        .unwrap();
+    let p = jl.eval_string("Point(1.0, 2.0, [\"a\", \"b\"])").unwrap();
+    let p: Point = p.extract().unwrap();
+    println!("{:?}", p);
+    assert_eq!(
+        p,
+        Point {
+            x: 1.0,
+            y: 2.0,
+            tags: vec!["a".to_owned(), "b".to_owned()],
+        }
+    );
+}
@@ -0,0 +1,72 @@
+use std::convert::TryFrom;
+
+use julia::api::{Datatype, JlValue, Julia, Value};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    jl.eval_string("mutable struct Point; x::Int; const y::Int; end")
+        .unwrap();
+    jl.eval_string("struct Frozen; x::Int; end").unwrap();
+
+    let p = jl.eval_string("Point(1, 2)").unwrap();
+    p.set("x", &Value::from(10)).unwrap();
+    assert_eq!(i64::try_from(&p.get("x").unwrap()).unwrap(), 10);
+
+    match p.set("y", &Value::from(20)) {
+        Err(Error::ImmutableField(name)) => assert_eq!(name, "y"),
+        other => panic!("expected ImmutableField, got {:?}", other),
+    }
+
+    let f = jl.eval_string("Frozen(1)").unwrap();
+    match f.set("x", &Value::from(2)) {
+        Err(Error::ImmutableField(name)) => assert_eq!(name, "x"),
+        other => panic!("expected ImmutableField, got {:?}", other),
+    }
+
+    jl.eval_string("struct Inner; port::Int; end; struct Config; server::Inner; end")
+        .unwrap();
+    let config = jl.eval_string("Config(Inner(8080))").unwrap();
+    let port = config.get_path("server.port").unwrap();
+    assert_eq!(i64::try_from(&port).unwrap(), 8080);
+    match config.get_path("server.host") {
+        Err(Error::UndefVar(path)) => assert_eq!(path, "server.host"),
+        other => panic!("expected UndefVar, got {:?}", other),
+    }
+
+    jl.eval_string("mutable struct Counter; @atomic n::Int; label::String; end")
+        .unwrap();
+    let c = jl.eval_string("Counter(0, \"c\")").unwrap();
+    c.set_atomic("n", &Value::from(5i64)).unwrap();
+    assert_eq!(i64::try_from(&c.get("n").unwrap()).unwrap(), 5);
+    match c.set("n", &Value::from(6i64)) {
+        Err(Error::InvalidArgument(msg)) => println!("{}", msg),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+    match c.set_atomic("label", &Value::from("d")) {
+        Err(Error::InvalidArgument(msg)) => println!("{}", msg),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    for (name, value) in p.fields().unwrap() {
+        println!("{} = {}", name, value);
+    }
+    let names: Vec<_> = p.fields().unwrap().into_iter().map(|(n, _)| n).collect();
+    assert_eq!(names, ["x", "y"]);
+
+    jl.eval_string("mutable struct Lazy; x::Vector{Int}; Lazy() = new(); end")
+        .unwrap();
+    let lazy = jl.eval_string("Lazy()").unwrap();
+    assert!(lazy.fields().unwrap()[0].1.is_nothing());
+
+    let t = jl.eval_string("(1, 2.0)").unwrap();
+    let names: Vec<_> = t.fields().unwrap().into_iter().map(|(n, _)| n).collect();
+    assert_eq!(names, ["1", "2"]);
+
+    let point = Datatype::from_value(jl.eval_string("Point").unwrap()).unwrap();
+    for sig in point.constructors().unwrap() {
+        println!("{}", sig);
+    }
+    println!("ok");
+}
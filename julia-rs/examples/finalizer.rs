@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use julia::api::{JlValue, Julia};
+
+/// Returns a finalizer counting its runs in `count`.
+fn counter(count: &Arc<AtomicUsize>) -> impl FnOnce() + Send + 'static {
+    let count = count.clone();
+    move || {
+        count.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    // finalizing runs the closure right away, collecting doesn't run it again
+    let finalized = Arc::new(AtomicUsize::new(0));
+    let x = jl.eval_string("Ref(1)").unwrap();
+    jl.gc_mut().add_finalizer(&x, counter(&finalized)).unwrap();
+    x.finalize().unwrap();
+    assert_eq!(finalized.load(Ordering::SeqCst), 1);
+    jl.gc_mut().collect(true).unwrap();
+    assert_eq!(finalized.load(Ordering::SeqCst), 1);
+
+    // dropping the handle lets Julia collect the object, once
+    let collected = Arc::new(AtomicUsize::new(0));
+    let y = jl.eval_string("Ref(2)").unwrap();
+    jl.gc_mut().add_finalizer(&y, counter(&collected)).unwrap();
+    drop(y);
+    jl.eval_string("GC.gc(); yield()").unwrap();
+    assert_eq!(collected.load(Ordering::SeqCst), 1);
+    jl.eval_string("GC.gc(); yield()").unwrap();
+    assert_eq!(collected.load(Ordering::SeqCst), 1);
+
+    // immutable values can't have finalizers
+    let x = jl.eval_string("1").unwrap();
+    assert!(jl.gc_mut().add_finalizer(&x, || ()).is_err());
+}
@@ -0,0 +1,24 @@
+use julia::api::{GcMode, Julia};
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    // prints GC timings to stderr while enabled
+    jl.gc_mut().enable_logging(true);
+    jl.eval_string("[zeros(1000) for _ in 1:1000]; GC.gc()")
+        .unwrap();
+    jl.gc_mut().enable_logging(false);
+    jl.gc_mut().collect(true).unwrap();
+    jl.gc_mut().collect_mode(GcMode::Incremental).unwrap();
+    jl.gc_mut().collect_mode(GcMode::Auto).unwrap();
+
+    // a long Rust loop which rarely calls into Julia
+    let mut sum = 0u64;
+    for i in 0..10_000_000u64 {
+        sum = sum.wrapping_add(i * i);
+        if i % 100_000 == 0 {
+            jl.gc().safepoint();
+        }
+    }
+    println!("{}", sum);
+}
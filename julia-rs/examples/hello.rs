@@ -1,7 +1,30 @@
-use julia::api::Julia;
+use julia::api::{julia_version, Julia};
 
 fn main() {
+    assert!(julia_version().is_none());
     let mut jl = Julia::new().unwrap();
-    let result = jl.eval_string("println(\"Hello, world!\")");
-    assert!(result.is_ok());
+    println!("{}", jl.version());
+    assert_eq!(julia_version().unwrap().major, jl.version().major);
+
+    println!("{}", jl.banner().unwrap());
+    println!(
+        "{} on {} threads",
+        jl.machine().unwrap(),
+        jl.cpu_threads().unwrap()
+    );
+    assert!(jl.cpu_threads().unwrap() > 0);
+    println!(
+        "generating code for {} ({}), generic: {}",
+        jl.cpu_target().unwrap(),
+        jl.cpu_name().unwrap(),
+        jl.is_generic_target().unwrap()
+    );
+
+    jl.run("println(\"Hello, world!\")").unwrap();
+
+    // the runtime keeps running after the handle is gone
+    jl.leak();
+    assert!(Julia::is_initialized());
+    let mut jl = unsafe { Julia::new_unchecked() };
+    jl.run("println(\"Still here!\")").unwrap();
 }
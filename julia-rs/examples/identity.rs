@@ -0,0 +1,28 @@
+use std::collections::HashSet;
+
+use julia::api::{Julia, Value, ValueById};
+
+// the hash of a ValueById never changes, even though Value has interior
+// mutability
+#[allow(clippy::mutable_key_type)]
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let a = jl.eval_string("a = [1, 2]").unwrap();
+    let a_again = jl.eval_string("a").unwrap();
+    let b = jl.eval_string("[1, 2]").unwrap();
+
+    let mut set = HashSet::new();
+    set.insert(ValueById(a));
+    // the same array is deduplicated, an equal copy isn't
+    assert!(!set.insert(ValueById(a_again)));
+    assert!(set.insert(ValueById(b)));
+
+    // immutable values are compared by content
+    assert!(set.insert(ValueById(Value::from(1i64))));
+    assert!(!set.insert(ValueById(Value::from(1i64))));
+    assert!(set.insert(ValueById(Value::from(1.0))));
+    assert!(set.insert(ValueById(Value::from("x"))));
+    assert!(!set.insert(ValueById(Value::from("x"))));
+    assert_eq!(set.len(), 5);
+}
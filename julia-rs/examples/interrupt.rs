@@ -0,0 +1,42 @@
+use std::thread;
+use std::time::Duration;
+
+use julia::api::{Exception, InterruptHandle, JlValue, Julia, Symbol};
+use julia::error::Error;
+
+/// Interrupts Julia from another thread after `ms` milliseconds.
+fn interrupt_after(handle: InterruptHandle, ms: u64) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(ms));
+        handle.interrupt().unwrap();
+    })
+}
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+    // interrupts go through Julia's event loop, not SIGINT
+    jl.install_sigint_handler(false);
+
+    // cancel a long computation from a control thread
+    let control = interrupt_after(jl.interrupt_handle().unwrap(), 500);
+    match jl.eval_string("while true; sleep(0.1); end") {
+        Err(Error::UnhandledException(Exception::Interrupt(_))) => println!("interrupted"),
+        other => panic!("expected InterruptException, got {:?}", other),
+    }
+    control.join().unwrap();
+
+    // Julia code can catch the interrupt and clean up
+    let control = interrupt_after(jl.interrupt_handle().unwrap(), 500);
+    let ret = jl
+        .eval_string(
+            "try sleep(5); :done; catch e; e isa InterruptException || rethrow(); :cancelled; end",
+        )
+        .unwrap();
+    control.join().unwrap();
+    assert!(Symbol::from_value(ret).unwrap() == Symbol::with_name("cancelled").unwrap());
+
+    // once Julia is shut down the handle reports an error instead
+    let handle = jl.interrupt_handle().unwrap();
+    jl.shutdown().unwrap();
+    assert!(handle.interrupt().is_err());
+}
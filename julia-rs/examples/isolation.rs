@@ -0,0 +1,31 @@
+use std::convert::TryFrom;
+
+use julia::api::{Exception, Julia};
+use julia::error::Error;
+use julia::sys::jl_eval_string;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    // an exception thrown through the raw API and not checked yet
+    let stale = unsafe { jl_eval_string(c"error(\"stale\")".as_ptr()) };
+    assert!(stale.is_null());
+    assert!(Exception::occurred());
+
+    // unrelated operations neither see nor clear it
+    let x = jl.eval_string("1 + 1").unwrap();
+    assert_eq!(i64::try_from(&x).unwrap(), 2);
+    match jl.eval_string("sqrt(-1.0)") {
+        Err(Error::UnhandledException(Exception::Domain(_))) => (),
+        other => panic!("expected DomainError, got {:?}", other),
+    }
+    let sqrt = jl.base().function("sqrt").unwrap();
+    assert!(sqrt.call1(&x).is_ok());
+
+    // the stale exception is still there for its owner to handle
+    match Exception::catch() {
+        Some(ex) => assert_eq!(ex.message().unwrap(), "stale"),
+        None => panic!("stale exception was lost"),
+    }
+    assert!(!Exception::occurred());
+}
@@ -0,0 +1,51 @@
+use std::convert::TryFrom;
+
+use julia::api::{Array, JlValue, Julia};
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let squares = jl.eval_string("(x^2 for x in 1:5)").unwrap();
+    let squares: Vec<i64> = squares
+        .iterate()
+        .unwrap()
+        .map(|x| i64::try_from(&x.unwrap()).unwrap())
+        .collect();
+    assert_eq!(squares, [1, 4, 9, 16, 25]);
+    println!("squares = {:?}", squares);
+
+    // consuming an Array works for boxed and bits elements alike
+    let words = Array::from_value(jl.eval_string("[\"a\", \"bc\"]").unwrap()).unwrap();
+    let mut total = 0;
+    for word in words {
+        total += String::try_from(&word.unwrap()).unwrap().len();
+    }
+    assert_eq!(total, 3);
+    let floats = Array::from_value(jl.eval_string("[1.5 2.5; 3.5 4.5]").unwrap()).unwrap();
+    let floats: Vec<f64> = floats
+        .into_iter()
+        .map(|x| f64::try_from(&x.unwrap()).unwrap())
+        .collect();
+    assert_eq!(floats, [1.5, 3.5, 2.5, 4.5]);
+    // iterators over the same Array root it independently
+    let nums = Array::from_value(jl.eval_string("[1, 2, 3]").unwrap()).unwrap();
+    let mut first = nums.clone().into_iter();
+    let second = nums.into_iter();
+    first.next();
+    drop(first);
+    jl.gc_mut().collect(true).unwrap();
+    assert_eq!(second.filter_map(|x| x.ok()).count(), 3);
+    let not_array = Array::from_value(jl.eval_string("1:3").unwrap()).unwrap();
+    assert!(not_array.into_iter().next().unwrap().is_err());
+    let holes = Array::from_value(jl.eval_string("Vector{Any}(undef, 1)").unwrap()).unwrap();
+    assert!(holes.into_iter().next().unwrap().is_err());
+
+    let empty = jl.eval_string("1:0").unwrap();
+    assert_eq!(empty.iterate().unwrap().count(), 0);
+
+    // not iterable, iterate throws a MethodError on the first step
+    let x = jl.eval_string("nothing").unwrap();
+    let mut it = x.iterate().unwrap();
+    assert!(it.next().unwrap().is_err());
+    assert!(it.next().is_none());
+}
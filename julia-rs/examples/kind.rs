@@ -0,0 +1,44 @@
+use std::convert::TryFrom;
+
+use julia::api::{Function, FunctionKind, JlValue, Julia};
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let plus = jl.base().function("+").unwrap();
+    assert_eq!(plus.kind().unwrap(), FunctionKind::Generic);
+    assert_eq!(String::try_from(&plus.name().unwrap()).unwrap(), "+");
+
+    let add_int = Function::from_value(jl.eval_string("Core.Intrinsics.add_int").unwrap()).unwrap();
+    assert_eq!(add_int.kind().unwrap(), FunctionKind::Intrinsic);
+
+    let getfield = jl.core().function("getfield").unwrap();
+    assert_eq!(getfield.kind().unwrap(), FunctionKind::Builtin);
+
+    let anon = Function::from_value(jl.eval_string("x -> x + 1").unwrap()).unwrap();
+    assert_eq!(anon.kind().unwrap(), FunctionKind::Closure);
+
+    let closure =
+        Function::from_value(jl.eval_string("let y = 2; x -> x + y; end").unwrap()).unwrap();
+    assert_eq!(closure.kind().unwrap(), FunctionKind::Closure);
+
+    let int = jl.base().function("Int").unwrap();
+    assert_eq!(int.kind().unwrap(), FunctionKind::Other);
+
+    // callable structs aren't Functions, but can be called
+    jl.run("struct Adder; n::Int; end; (a::Adder)(x) = a.n + x")
+        .unwrap();
+    assert!(jl.eval_string("Adder(1)").unwrap().is_callable());
+    assert!(jl.eval_string("Adder").unwrap().is_callable());
+    assert!(jl
+        .eval_string("Core.Intrinsics.add_int")
+        .unwrap()
+        .is_callable());
+    assert!(jl.eval_string("x -> x").unwrap().is_callable());
+    assert!(!jl
+        .eval_string("function nomethods end")
+        .unwrap()
+        .is_callable());
+    assert!(!jl.eval_string("1.5").unwrap().is_callable());
+    println!("ok");
+}
@@ -0,0 +1,72 @@
+use std::convert::TryFrom;
+
+use julia::api::{Array, Datatype, Exception, JlValue, Julia, Value};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let a = Array::from_value(jl.eval_string("[1.0 2.0; 3.0 4.0]").unwrap()).unwrap();
+    let id = Array::from_value(jl.eval_string("[1.0 0.0; 0.0 1.0]").unwrap()).unwrap();
+
+    let b = a.matmul(&id).unwrap();
+    assert_eq!(f64::try_from(&b.at2(1, 0).get().unwrap()).unwrap(), 3.0);
+
+    let t = a.transpose().unwrap();
+    assert_eq!(f64::try_from(&t.at2(0, 1).get().unwrap()).unwrap(), 3.0);
+
+    let det = f64::try_from(&a.det().unwrap()).unwrap();
+    assert!((det + 2.0).abs() < 1e-12);
+
+    let inv = a.inv().unwrap();
+    let check = a.matmul(&inv).unwrap();
+    assert!((f64::try_from(&check.at2(0, 0).get().unwrap()).unwrap() - 1.0).abs() < 1e-12);
+
+    let v = Array::from_value(jl.eval_string("[1.0, 2.0, 3.0]").unwrap()).unwrap();
+    match a.matmul(&v) {
+        Err(Error::UnhandledException(Exception::DimensionMismatch(_))) => (),
+        other => panic!("expected DimensionMismatch, got {:?}", other),
+    }
+
+    let singular = Array::from_value(jl.eval_string("[1.0 2.0; 2.0 4.0]").unwrap()).unwrap();
+    match singular.inv() {
+        Err(Error::UnhandledException(Exception::Singular(_))) => (),
+        other => panic!("expected SingularException, got {:?}", other),
+    }
+
+    let z = Array::zeros(&Datatype::int32(), &[2, 3]).unwrap();
+    assert_eq!(z.ndims().unwrap(), 2);
+    assert_eq!((z.dim(0).unwrap(), z.dim(1).unwrap()), (2, 3));
+    let eltype = z.element_type().unwrap();
+    assert_eq!(eltype.lock().unwrap(), Datatype::int32().lock().unwrap());
+
+    let o = Array::ones_f64(&[4]).unwrap();
+    assert_eq!(o.len().unwrap(), 4);
+    assert_eq!(f64::try_from(&o.at(3).get().unwrap()).unwrap(), 1.0);
+    let eltype = o.element_type().unwrap();
+    assert_eq!(eltype.lock().unwrap(), Datatype::float64().lock().unwrap());
+    let m = Array::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+    let expected = jl.eval_string("[1.0 2.0 3.0; 4.0 5.0 6.0]").unwrap();
+    let isequal = jl.base().function("isequal").unwrap();
+    let m_value = Value::new(m.lock().unwrap() as *mut _).unwrap();
+    let same = isequal.call2(&m_value, &expected).unwrap();
+    assert!(bool::try_from(&same).unwrap());
+    assert_eq!(m.dims().unwrap(), [2, 3]);
+    let p = m.permutedims(&[1, 0]).unwrap();
+    assert_eq!(p.dims().unwrap(), [3, 2]);
+    assert_eq!(f64::try_from(&p.at2(2, 0).get().unwrap()).unwrap(), 3.0);
+    let cube = Array::zeros_f64(&[2, 3, 4]).unwrap();
+    assert_eq!(
+        cube.permutedims(&[2, 0, 1]).unwrap().dims().unwrap(),
+        [4, 2, 3]
+    );
+    assert!(m.permutedims(&[0, 0]).is_err());
+    assert!(m.permutedims(&[0]).is_err());
+    assert!(m.permutedims(&[0, 2]).is_err());
+
+    match Array::from_rows(&[vec![1.0, 2.0], vec![3.0]]) {
+        Err(Error::InvalidArgument(msg)) => println!("{}", msg),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+    println!("ok");
+}
@@ -1,17 +1,142 @@
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 
-use julia::api::{Julia, Value};
+use julia::api::{Datatype, Exception, Julia, Value};
+use julia::error::Error;
 
 fn main() {
-    let jl = Julia::new().unwrap();
+    let mut jl = Julia::new().unwrap();
 
     let sqrt = jl.base().function("sqrt").unwrap();
     let x = 3.0;
-    let y = {
-        let x = Value::from(x);
-        sqrt.call1(&x).unwrap()
-    };
-    let y = f64::try_from(&y).unwrap();
+    let y: f64 = sqrt.call_into(&[&Value::from(x)]).unwrap();
 
     println!("sqrt({}) = {}", x, y);
+
+    let max = jl.base().function("max").unwrap();
+    let m = max.call_with((1.5, 2i64, 0u8)).unwrap();
+    assert_eq!(f64::try_from(&m).unwrap(), 2.0);
+    let s = jl.base().function("string").unwrap();
+    let s = s.call_with(("x = ", 1i64, ';', true)).unwrap();
+    assert_eq!(String::try_from(&s).unwrap(), "x = 1;true");
+    let time_ns = jl.base().function("time_ns").unwrap();
+    assert!(time_ns.call_with(()).is_ok());
+
+    let math_sqrt = jl.function("Base.Math.sqrt").unwrap();
+    let y: f64 = math_sqrt.call_into(&[&Value::from(16.0)]).unwrap();
+    assert_eq!(y, 4.0);
+    jl.run("using LinearAlgebra").unwrap();
+    assert!(jl.function("LinearAlgebra.det").is_ok());
+    match jl.function("Base.Maths.sqrt") {
+        Err(Error::UndefVar(name)) => assert_eq!(name, "Base.Maths"),
+        other => panic!("expected UndefVar, got {:?}", other),
+    }
+    match jl.function("Base.pi") {
+        Err(Error::NotAFunction) => (),
+        other => panic!("expected NotAFunction, got {:?}", other),
+    }
+    match jl.function("Base.sqrt.x") {
+        Err(Error::InvalidArgument(_)) => (),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    let a = Value::from(2.0);
+    let b = Value::from(3.0);
+    let c = (&a * &b).unwrap();
+    let c = f64::try_from(&c).unwrap();
+
+    println!("{} * {} = {}", 2.0, 3.0, c);
+
+    let mul = jl.base().function("*").unwrap();
+    let args = [a.as_raw().unwrap(), b.as_raw().unwrap()];
+    let mut sum = 0.0;
+    for _ in 0..1000 {
+        // a and b outlive the loop, which keeps the raw pointers valid
+        let c = unsafe { mul.call_raw(&args) }.unwrap();
+        sum += f64::try_from(&c).unwrap();
+    }
+    assert_eq!(sum, 6000.0);
+
+    // there is no method matching +(::Float64, ::String)
+    assert!((&a + &Value::from("x")).is_err());
+
+    let divrem = jl.base().function("divrem").unwrap();
+    let qr = divrem
+        .call_tuple(&[&Value::from(7i64), &Value::from(2i64)])
+        .unwrap();
+    let qr: Vec<i64> = qr.iter().map(|x| i64::try_from(x).unwrap()).collect();
+    assert_eq!(qr, [3, 1]);
+    match sqrt.call_tuple(&[&Value::from(4.0)]) {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+
+    let promoted = Value::promote(&[&Value::from(1i64), &Value::from(2.5)]).unwrap();
+    let promoted: Vec<f64> = promoted.iter().map(|x| f64::try_from(x).unwrap()).collect();
+    println!("promote(1, 2.5) = {:?}", promoted);
+    assert_eq!(promoted, [1.0, 2.5]);
+
+    assert!(Value::promote(&[]).unwrap().is_empty());
+    assert_eq!(Value::promote(&[&a]).unwrap().len(), 1);
+    assert!(Value::promote(&[&a, &Value::from("x")]).is_err());
+
+    assert!(Value::from(f64::NAN).is_nan().unwrap());
+    assert!(Value::from(f32::INFINITY).is_inf().unwrap());
+    assert!(a.is_finite().unwrap());
+    let z = jl.eval_string("complex(1.0, NaN)").unwrap();
+    assert!(z.is_nan().unwrap());
+    assert!(!jl.eval_string("Float16(1)").unwrap().is_inf().unwrap());
+    match Value::from("x").is_nan() {
+        Err(Error::InvalidArgument(_)) => (),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    let mut xs = [
+        Value::from(3i64),
+        Value::from(f64::NAN),
+        Value::from(1.5),
+        Value::from(-2i32),
+    ];
+    xs.sort_by(|a, b| a.cmp_julia(b).unwrap());
+    let sorted: Vec<String> = xs.iter().map(|x| x.to_string()).collect();
+    assert_eq!(sorted, ["-2", "1.5", "3", "NaN"]);
+    assert_eq!(
+        Value::from(1i64).cmp_julia(&Value::from(1.0)).unwrap(),
+        Ordering::Equal
+    );
+    let nan = Value::from(f64::NAN);
+    assert_eq!(nan.cmp_julia(&nan).unwrap(), Ordering::Equal);
+    assert_eq!(
+        Value::from(0.0).cmp_julia(&Value::from(-0.0)).unwrap(),
+        Ordering::Equal
+    );
+    let missing = jl.eval_string("missing").unwrap();
+    assert_eq!(
+        Value::from(1i64).cmp_julia(&missing).unwrap(),
+        Ordering::Less
+    );
+    match Value::from(1i64).cmp_julia(&Value::from("x")) {
+        Err(Error::MethodError(_)) => (),
+        other => panic!("expected MethodError, got {:?}", other),
+    }
+
+    assert!(nan.isequal(&nan).unwrap());
+    assert!(!Value::from(0.0).isequal(&Value::from(-0.0)).unwrap());
+    let make = "mutable struct Point; x; y; end; [Point(1, [2.0, NaN])]";
+    let p = jl.eval_string(make).unwrap();
+    let q = jl.eval_string("[Point(1, [2.0, NaN])]").unwrap();
+    assert!(!p.isequal(&q).unwrap());
+    assert!(p.deep_equal(&q).unwrap());
+    let r = jl.eval_string("[Point(1, [2.0, 3.0])]").unwrap();
+    assert!(!p.deep_equal(&r).unwrap());
+
+    assert_eq!(a.sizeof().unwrap(), 8);
+    assert_eq!(Value::from("hello").sizeof().unwrap(), 5);
+
+    let x = Value::parse(&Datatype::float64(), "1e3").unwrap();
+    assert_eq!(f64::try_from(&x).unwrap(), 1000.0);
+    match Value::parse(&Datatype::int64(), "x") {
+        Err(Error::UnhandledException(Exception::Argument(_))) => (),
+        other => panic!("expected ArgumentError, got {:?}", other),
+    }
 }
@@ -0,0 +1,38 @@
+use std::convert::TryFrom;
+
+use julia::api::{Array, Datatype, JlValue, Julia, Value};
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let plus = jl.base().function("+").unwrap();
+    let plus_one = plus.partial(&[&Value::from(1)]).unwrap();
+
+    let map = jl.base().function("map").unwrap();
+    let xs = jl.eval_string("Any[1, 2, 3]").unwrap();
+    let ys = map
+        .call2(&Value::from_value(plus_one).unwrap(), &xs)
+        .unwrap();
+    // as_vec needs boxed elements, so collect into a Vector{Any} first
+    let any = Value::from_value(Datatype::any()).unwrap();
+    let collect = jl.base().function("collect").unwrap();
+    let ys = Array::from_value(collect.call2(&any, &ys).unwrap()).unwrap();
+
+    let ys: Vec<i64> = ys
+        .as_vec()
+        .unwrap()
+        .iter()
+        .map(|y| i64::try_from(y).unwrap())
+        .collect();
+    assert_eq!(ys, [2, 3, 4]);
+    println!("map(+(1), [1, 2, 3]) = {:?}", ys);
+
+    // methods defined at runtime are visible in the latest world
+    jl.eval_string("area(r) = pi * r^2").unwrap();
+    let area = jl.main().function("area").unwrap();
+    let a = f64::try_from(&area.call_latest(&[&Value::from(1.0)]).unwrap()).unwrap();
+    assert!((a - std::f64::consts::PI).abs() < 1e-12);
+    jl.eval_string("area(r::Int) = 3r^2").unwrap();
+    let a = i64::try_from(&area.call_latest(&[&Value::from(2i64)]).unwrap()).unwrap();
+    assert_eq!(a, 12);
+}
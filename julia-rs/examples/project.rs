@@ -0,0 +1,29 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+
+use julia::api::Julia;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let dir = std::env::temp_dir().join("julia-rs-project-example");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("Project.toml"), "name = \"Example\"\n").unwrap();
+
+    let before = jl.active_project().unwrap();
+    let inside = jl
+        .with_project(&dir, |jl| jl.active_project().unwrap())
+        .unwrap();
+    assert_eq!(inside, Some(dir.join("Project.toml")));
+    assert_eq!(jl.active_project().unwrap(), before);
+
+    // the previous project is restored even if the closure panics
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        jl.with_project(&dir, |_| panic!("oops")).unwrap();
+    }));
+    assert!(res.is_err());
+    assert_eq!(jl.active_project().unwrap(), before);
+    println!("active project: {:?}", before);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
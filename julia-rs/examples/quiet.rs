@@ -0,0 +1,20 @@
+use std::convert::TryFrom;
+
+use julia::api::{Julia, JuliaOptions};
+
+fn main() {
+    // nothing is printed before the first line below
+    let mut jl = Julia::with_options(&JuliaOptions::new().quiet(true)).unwrap();
+    println!("initialized");
+
+    let quiet = jl.eval_string("Base.JLOptions().quiet").unwrap();
+    assert_eq!(i8::try_from(&quiet).unwrap(), 1);
+    let depwarn = jl.eval_string("Base.JLOptions().depwarn").unwrap();
+    assert_eq!(i8::try_from(&depwarn).unwrap(), 0);
+
+    // deprecations stay silent
+    let diag = jl
+        .eval_with_diagnostics("Base.depwarn(\"old\", :old); 1")
+        .unwrap();
+    assert!(diag.warnings.is_empty());
+}
@@ -0,0 +1,28 @@
+use std::convert::TryFrom;
+use std::ptr::NonNull;
+
+use julia::api::{Julia, Value};
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    jl.eval_string("incr!(r) = (r[] += 1; nothing)").unwrap();
+    let incr = jl.main().function("incr!").unwrap();
+
+    let r = Value::new_ref(&Value::from(41i64)).unwrap();
+    incr.call1(&r).unwrap();
+    assert_eq!(i64::try_from(&r.deref_ref().unwrap()).unwrap(), 42);
+
+    // untyped refs accept values of any type
+    let r = Value::new_ref_any(&Value::from(1i64)).unwrap();
+    let setindex = jl.base().function("setindex!").unwrap();
+    setindex.call2(&r, &Value::from("one")).unwrap();
+    assert_eq!(String::try_from(&r.deref_ref().unwrap()).unwrap(), "one");
+
+    assert!(Value::from(1i64).deref_ref().is_err());
+    let x = Value::from(7i64);
+    let ptr = NonNull::new(x.as_raw().unwrap()).unwrap();
+    let y = Value::from_non_null(ptr);
+    assert_eq!(i64::try_from(&y).unwrap(), 7);
+    println!("ok");
+}
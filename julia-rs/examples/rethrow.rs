@@ -0,0 +1,30 @@
+use julia::api::{Exception, Julia};
+use julia::error::Error;
+use julia::jl_eval;
+
+/// Called from Julia, runs Julia code and passes its exception back.
+extern "C" fn fail() {
+    let ex = {
+        let mut jl = unsafe { Julia::new_unchecked() };
+        match jl.eval_string("sqrt(-1.0)") {
+            Err(Error::UnhandledException(ex)) => ex,
+            other => panic!("expected an exception, got {:?}", other),
+        }
+    };
+    unsafe { ex.rethrow() }
+}
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let caught = jl_eval!(
+        jl,
+        "try ccall(Ptr{{Cvoid}}({}), Cvoid, ()); catch e; e; end",
+        fail as extern "C" fn() as usize
+    )
+    .unwrap();
+    match Exception::with_value(caught).unwrap() {
+        Exception::Domain(_) => println!("caught DomainError"),
+        other => panic!("expected DomainError, got {:?}", other),
+    }
+}
@@ -0,0 +1,37 @@
+use std::convert::TryFrom;
+
+use julia::api::{Array, JlValue, Julia};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    jl.eval_string("struct Sample; name::String; data::Vector{Float64}; end")
+        .unwrap();
+    let sample = jl.eval_string("Sample(\"a\", [1.0, 2.0, 3.0])").unwrap();
+
+    let bytes = sample.serialize().unwrap();
+    println!("serialized to {} bytes", bytes.len());
+
+    let copy = jl.deserialize(&bytes).unwrap();
+    assert_eq!(copy.typename().unwrap(), "Sample");
+    assert_eq!(String::try_from(&copy.get("name").unwrap()).unwrap(), "a");
+    let isequal = jl.base().function("isequal").unwrap();
+    let same = isequal
+        .call2(&sample.get("data").unwrap(), &copy.get("data").unwrap())
+        .unwrap();
+    assert!(bool::try_from(&same).unwrap());
+
+    // pass the blob to Julia and back as a Vector{UInt8}
+    let blob = Array::from_bytes(&bytes).unwrap();
+    assert_eq!(blob.as_bytes().unwrap(), &bytes[..]);
+    assert_eq!(blob.len().unwrap(), bytes.len());
+    let floats = Array::from_value(jl.eval_string("[1.0, 2.0]").unwrap()).unwrap();
+    match floats.as_bytes() {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+
+    // garbage isn't a valid stream
+    assert!(jl.deserialize(&[1, 2, 3]).is_err());
+}
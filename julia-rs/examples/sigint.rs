@@ -0,0 +1,29 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use julia::api::{JlValue, Julia, JuliaOptions, Symbol};
+
+static CAUGHT: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_sigint(_: libc::c_int) {
+    CAUGHT.store(true, Ordering::SeqCst);
+}
+
+fn main() {
+    // Julia installs no signal handlers, so Ctrl-C belongs to the host
+    let mut jl = Julia::with_options(&JuliaOptions::new().handle_signals(false)).unwrap();
+    jl.install_sigint_handler(false);
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            on_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+        libc::raise(libc::SIGINT);
+    }
+    assert!(CAUGHT.load(Ordering::SeqCst));
+
+    // Julia code carries on without an InterruptException
+    let ret = jl
+        .eval_string("try sleep(0.1); :done; catch e; e isa InterruptException ? :interrupted : rethrow(); end")
+        .unwrap();
+    assert!(Symbol::from_value(ret).unwrap() == Symbol::with_name("done").unwrap());
+}
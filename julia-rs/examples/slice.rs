@@ -0,0 +1,80 @@
+use std::convert::TryFrom;
+
+use julia::api::{Array, Datatype, Exception, JlValue, Julia, Value};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    let xs = Array::from_value(jl.eval_string("[10, 20, 30, 40, 50]").unwrap()).unwrap();
+    let eltype = xs.element_type().unwrap();
+    assert_eq!(eltype.lock().unwrap(), Datatype::int64().lock().unwrap());
+
+    assert_eq!(format!("{:?}", xs), "Array{Int64, 1}(5)[10, 20, 30, 40, …]");
+    let big = Array::zeros_f64(&[1000, 1000]).unwrap();
+    println!("{:?}", big);
+
+    // reserve room up front, pushing then doesn't reallocate
+    let acc = Array::with_capacity(&Datatype::float64(), 100).unwrap();
+    assert_eq!(acc.len().unwrap(), 0);
+    let cap = acc.capacity().unwrap();
+    assert!(cap >= 100);
+    for i in 0..100 {
+        acc.push(&Value::from(i as f64)).unwrap();
+    }
+    assert_eq!(acc.len().unwrap(), 100);
+    assert_eq!(acc.capacity().unwrap(), cap);
+    assert!(big.capacity().is_err());
+
+    let view = xs.slice(1..4).unwrap();
+
+    let sum = jl.base().function("sum").unwrap();
+    assert_eq!(i64::try_from(&sum.call1(&view).unwrap()).unwrap(), 90);
+
+    // writing to the view writes through to the parent
+    let setindex = jl.base().function("setindex!").unwrap();
+    setindex
+        .call3(&view, &Value::from(0i64), &Value::from(1i64))
+        .unwrap();
+    let getindex = jl.base().function("getindex").unwrap();
+    let parent = Value::new(xs.lock().unwrap() as *mut _).unwrap();
+    let second = getindex.call2(&parent, &Value::from(2i64)).unwrap();
+    assert_eq!(i64::try_from(&second).unwrap(), 0);
+
+    assert!(xs.slice(3..6).is_err());
+
+    let bytes = Array::from_bytes(&1.5f64.to_ne_bytes()).unwrap();
+    let floats = bytes.reinterpret(&Datatype::float64()).unwrap();
+    let first = getindex.call2(&floats, &Value::from(1i64)).unwrap();
+    assert_eq!(f64::try_from(&first).unwrap(), 1.5);
+    let odd = Array::from_bytes(&[0; 12]).unwrap();
+    match odd.reinterpret(&Datatype::float64()) {
+        Err(Error::UnhandledException(Exception::Argument(_))) => (),
+        other => panic!("expected ArgumentError, got {:?}", other),
+    }
+    let strings = Array::from_value(jl.eval_string("[\"a\"]").unwrap()).unwrap();
+    match strings.reinterpret(&Datatype::uint8()) {
+        Err(Error::InvalidArgument(_)) => (),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    let mut buf = [0i64; 8];
+    assert_eq!(xs.copy_to_slice(&mut buf).unwrap(), 5);
+    assert_eq!(buf[..5], [10, 0, 30, 40, 50]);
+    match xs.copy_to_slice(&mut [0.0f64; 8]) {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+    assert!(xs.copy_to_slice(&mut [0i64; 2]).is_err());
+
+    // elements of small unions are stored inline with a selector byte each
+    let maybe =
+        Array::from_value(jl.eval_string("Union{Int, Nothing}[1, nothing]").unwrap()).unwrap();
+    assert!(maybe.is_bits_union().unwrap());
+    assert!(!xs.is_bits_union().unwrap());
+    match maybe.as_vec() {
+        Err(Error::InvalidUnbox) => (),
+        other => panic!("expected InvalidUnbox, got {:?}", other),
+    }
+    println!("ok");
+}
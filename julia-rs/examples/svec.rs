@@ -1,4 +1,5 @@
-use julia::{api::Julia, jlvec};
+use julia::api::{Julia, Svec, Value};
+use julia::jlvec;
 
 fn main() {
     let mut _jl = Julia::new().unwrap();
@@ -7,4 +8,10 @@ fn main() {
     println!("{}", jlvec![1, 2].unwrap());
     println!("{}", jlvec![1, 2, 3, 4].unwrap());
     println!("{}", jlvec![1; 8].unwrap());
+
+    let (a, b) = (Value::from(1.5), Value::from("b"));
+    let svec = Svec::from_values(&[&a, &b]).unwrap();
+    assert_eq!(svec.len().unwrap(), 2);
+    println!("{}", svec);
+    assert!(Svec::from_values(&[]).unwrap().is_empty());
 }
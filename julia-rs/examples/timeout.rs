@@ -0,0 +1,37 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use julia::api::{Julia, Value};
+use julia::error::Error;
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    jl.run("slow(x) = (sleep(10); x)").unwrap();
+    jl.run("fast(x) = (sleep(0.01); 2x)").unwrap();
+    let slow = jl.main().function("slow").unwrap();
+    let fast = jl.main().function("fast").unwrap();
+    let x = Value::from(21i64);
+
+    let y = fast.call_timeout(&[&x], Duration::from_secs(5)).unwrap();
+    assert_eq!(i64::try_from(&y).unwrap(), 42);
+
+    match slow.call_timeout(&[&x], Duration::from_millis(100)) {
+        Err(Error::Timeout) => println!("slow(21) timed out"),
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+
+    let (y, elapsed, bytes) = fast.call_timed(&[&x]).unwrap();
+    assert_eq!(i64::try_from(&y).unwrap(), 42);
+    assert!(elapsed >= Duration::from_millis(10));
+    println!("fast(21) took {:?} and allocated {} bytes", elapsed, bytes);
+
+    let zeros = jl.base().function("zeros").unwrap();
+    let (_, _, bytes) = zeros.call_timed(&[&Value::from(1000i64)]).unwrap();
+    assert!(bytes >= 8000);
+
+    // errors thrown by the callee are returned as usual
+    let error = jl.base().function("error").unwrap();
+    let res = error.call_timeout(&[&Value::from("oops")], Duration::from_secs(5));
+    assert!(matches!(res, Err(Error::UnhandledException(_))));
+}
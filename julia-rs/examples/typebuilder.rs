@@ -0,0 +1,44 @@
+use julia::api::datatype::TypeBuilder;
+use julia::api::{Datatype, Julia, Symbol, Value};
+use julia::error::Error;
+use julia::jlvec;
+
+fn main() {
+    let _jl = Julia::new().unwrap();
+
+    let fnames = jlvec![
+        Value::from_value(Symbol::with_name("x").unwrap()).unwrap(),
+        Value::from_value(Symbol::with_name("y").unwrap()).unwrap()
+    ]
+    .unwrap();
+    let ftypes = jlvec![
+        Value::from_value(Datatype::float64()).unwrap(),
+        Value::from_value(Datatype::float64()).unwrap()
+    ]
+    .unwrap();
+    let point = TypeBuilder::new()
+        .name("Point")
+        .fnames(&fnames)
+        .ftypes(&ftypes)
+        .build()
+        .unwrap();
+    assert_eq!(point.name().unwrap(), "Point");
+    assert!(point.is_concrete());
+
+    // a name without a type used to crash inside jl_new_datatype
+    let one_type = jlvec![Value::from_value(Datatype::int64()).unwrap()].unwrap();
+    let mismatched = TypeBuilder::new()
+        .name("Broken")
+        .fnames(&fnames)
+        .ftypes(&one_type)
+        .build();
+    match mismatched {
+        Err(Error::InvalidArgument(msg)) => println!("{}", msg),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+
+    match TypeBuilder::new().build() {
+        Err(Error::InvalidArgument(_)) => (),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+}
@@ -1,11 +1,19 @@
-use julia::api::{Exception, JlValue, Julia, Value};
+use std::collections::HashMap;
+
+use julia::api::{Datatype, Exception, JlValue, Julia, Symbol, UnionAll, Value};
 use julia::error::Error;
 
+// Datatype hashes by pointer, which the Mutex inside doesn't affect
+#[allow(clippy::mutable_key_type)]
 fn main() {
     let mut jl = Julia::new().unwrap();
 
     let x = Value::from(5.0);
     println!("typename(5.0) = {:?}", x.typename());
+    let float64 = Symbol::with_name("Float64").unwrap();
+    assert!(x.typename_symbol().unwrap() == float64);
+    assert!(Symbol::cached("Float64").unwrap() == float64);
+    assert!(Symbol::cached("Float64").unwrap() == float64);
 
     let x = jl.eval_string("x = 5").unwrap();
     println!("typename(x = 5) = {:?}", x.typename());
@@ -23,4 +31,53 @@ fn main() {
         _ => panic!(),
     };
     println!("typename(y) = {:?}", y.typename());
+
+    assert!(Datatype::number().is_abstract());
+    assert!(!Datatype::number().is_concrete());
+    assert!(Datatype::float64().is_concrete());
+    assert!(!Datatype::float64().is_mutable());
+    let dict = Datatype::from_value(jl.eval_string("Dict{Int, Int}").unwrap()).unwrap();
+    assert!(dict.is_mutable());
+    let same = Datatype::from_value(jl.eval_string("Dict{Int, Int}").unwrap()).unwrap();
+    assert_eq!(dict, same);
+    assert_ne!(dict, Datatype::float64());
+    let mut sizes = HashMap::new();
+    sizes.insert(Datatype::float64(), 8);
+    sizes.insert(Datatype::int32(), 4);
+    let x = jl.eval_string("1.5").unwrap();
+    assert_eq!(sizes[&x.datatype().unwrap()], 8);
+    assert_eq!(dict.name().unwrap(), "Dict");
+    assert_eq!(dict.qualified_name().unwrap(), "Base.Dict");
+    assert_eq!(
+        Datatype::float64().qualified_name().unwrap(),
+        "Core.Float64"
+    );
+    jl.eval_string("module Shapes; struct Dict end; end")
+        .unwrap();
+    let shapes = Datatype::from_value(jl.eval_string("Shapes.Dict").unwrap()).unwrap();
+    assert_eq!(shapes.qualified_name().unwrap(), "Main.Shapes.Dict");
+
+    for (src, kind) in [
+        ("Int", "DataType"),
+        ("Vector", "UnionAll"),
+        ("Union{Int, Nothing}", "Union"),
+        ("Union{}", "TypeofBottom"),
+    ] {
+        let t = jl.eval_string(src).unwrap();
+        assert_eq!(t.kind_name().unwrap(), kind);
+    }
+    assert!(Value::from(1).kind_name().is_err());
+
+    // peel `Array{T, N} where {T, N}` one type variable at a time
+    let array = UnionAll::from_value(jl.eval_string("Array").unwrap()).unwrap();
+    let t = array.var().unwrap();
+    assert!(t.is_typevar());
+    println!("var(Array) = {:?}", t);
+    let inner = UnionAll::from_value(array.body().unwrap()).unwrap();
+    println!("var(body(Array)) = {:?}", inner.var().unwrap());
+    let body = inner.body().unwrap();
+    assert_eq!(
+        Value::from_value(body).unwrap().kind_name().unwrap(),
+        "DataType"
+    );
 }
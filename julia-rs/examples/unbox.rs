@@ -0,0 +1,29 @@
+use julia::api::{JlValue, Julia, RustValue};
+
+fn main() {
+    let mut jl = Julia::new().unwrap();
+
+    for src in [
+        "42",
+        "Int8(-3)",
+        "1.5",
+        "1.5f0",
+        "true",
+        "\"hello\"",
+        "[1, 2]",
+    ] {
+        let x = jl.eval_string(src).unwrap();
+        match x.unbox_any().unwrap() {
+            RustValue::I64(x) => println!("{} => i64 {}", src, x),
+            RustValue::F64(x) => println!("{} => f64 {}", src, x),
+            RustValue::Bool(x) => println!("{} => bool {}", src, x),
+            RustValue::Str(x) => println!("{} => String {:?}", src, x),
+            RustValue::Other(x) => println!("{} => {}", src, x.typename().unwrap()),
+        }
+    }
+
+    let x = jl.eval_string("Int8(-3)").unwrap();
+    assert!(matches!(x.unbox_any().unwrap(), RustValue::I64(-3)));
+    let x = jl.eval_string("UInt8(3)").unwrap();
+    assert!(matches!(x.unbox_any().unwrap(), RustValue::Other(_)));
+}
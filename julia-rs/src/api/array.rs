@@ -1,18 +1,182 @@
 //! Module providing wrappers for iteratable sequences.
 
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+use std::ptr;
 use std::slice;
 
-use crate::api::{JlValue, Value};
-use crate::error::Result;
-use crate::{jlvalues, sys::*};
+use smallvec::{smallvec, SmallVec};
+
+use crate::api::{Datatype, Function, JlValue, Module, Number, Value};
+use crate::error::{Error, Result};
+use crate::{jlvalues, simple_jlvalue, sys::*};
+
+/// Loads the `LinearAlgebra` standard library, failing if it isn't
+/// available.
+fn linear_algebra() -> Result<Module> {
+    super::require("LinearAlgebra")
+}
 
 jlvalues! {
-    pub struct Array(jl_array_t);
     pub struct ByteArray(jl_array_t);
     pub struct Svec(jl_svec_t);
 }
 
+/// Primitive types which Julia stores inline in arrays, like `Float64`.
+pub trait ArrayElement: Number + Copy {
+    /// Returns the Julia type corresponding to the Rust type.
+    fn julia_type() -> *mut jl_datatype_t;
+}
+
+macro_rules! array_element {
+    ($($t:ty => $jl:ident),*) => {
+        $(
+            impl ArrayElement for $t {
+                fn julia_type() -> *mut jl_datatype_t {
+                    unsafe { $jl }
+                }
+            }
+        )*
+    }
+}
+
+array_element!(
+    bool => jl_bool_type,
+    i8 => jl_int8_type,
+    i16 => jl_int16_type,
+    i32 => jl_int32_type,
+    i64 => jl_int64_type,
+    u8 => jl_uint8_type,
+    u16 => jl_uint16_type,
+    u32 => jl_uint32_type,
+    u64 => jl_uint64_type,
+    f32 => jl_float32_type,
+    f64 => jl_float64_type
+);
+
+#[cfg(target_pointer_width = "64")]
+array_element!(isize => jl_int64_type, usize => jl_uint64_type);
+#[cfg(target_pointer_width = "32")]
+array_element!(isize => jl_int32_type, usize => jl_uint32_type);
+
+// Arrays can be huge, Debug only prints a summary
+simple_jlvalue!(Array, jl_array_t, custom_debug);
+
+/// Number of elements shown by `Debug` for Array.
+const DEBUG_PREVIEW_LEN: usize = 4;
+
 impl Array {
+    /// Calls `f(elty, dims...)`, e.g. `zeros(Float64, 2, 3)`.
+    fn with_dims(f: &str, elty: &Datatype, dims: &[usize]) -> Result<Self> {
+        let elty = Value::new(elty.lock()? as *mut jl_value_t)?;
+        let mut args = vec![elty];
+        args.extend(dims.iter().map(|&d| Value::from(d as i64)));
+        let array = Function::base(f)?.call(&args)?;
+        Self::from_value(array)
+    }
+
+    /// Creates an Array of zeros with element type `elty` and dimensions
+    /// `dims`.
+    pub fn zeros(elty: &Datatype, dims: &[usize]) -> Result<Self> {
+        Self::with_dims("zeros", elty, dims)
+    }
+
+    /// Creates an Array of ones with element type `elty` and dimensions
+    /// `dims`.
+    pub fn ones(elty: &Datatype, dims: &[usize]) -> Result<Self> {
+        Self::with_dims("ones", elty, dims)
+    }
+
+    /// Creates an Array of Float64 zeros with dimensions `dims`.
+    pub fn zeros_f64(dims: &[usize]) -> Result<Self> {
+        Self::zeros(&Datatype::float64(), dims)
+    }
+
+    /// Creates an Array of Float64 ones with dimensions `dims`.
+    pub fn ones_f64(dims: &[usize]) -> Result<Self> {
+        Self::ones(&Datatype::float64(), dims)
+    }
+
+    /// Allocates a 0-dimensional Array with element type `elty`, which
+    /// holds a single, uninitialized element.
+    pub fn alloc_0d(elty: &Datatype) -> Result<Self> {
+        let atype = unsafe { jl_apply_array_type(elty.lock()? as *mut jl_value_t, 0) };
+        jl_catch!();
+        let raw = unsafe { jl_new_array(atype, jl_emptytuple) };
+        jl_catch!();
+        Self::new(raw)
+    }
+
+    /// Allocates an empty Vector with element type `elty`, reserving room
+    /// for `cap` elements so pushing up to `cap` elements doesn't
+    /// reallocate.
+    pub fn with_capacity(elty: &Datatype, cap: usize) -> Result<Self> {
+        let atype = unsafe { jl_apply_array_type(elty.lock()? as *mut jl_value_t, 1) };
+        jl_catch!();
+        let raw = unsafe { jl_alloc_array_1d(atype, 0) };
+        jl_catch!();
+        unsafe { jl_array_sizehint(raw, cap) };
+        jl_catch!();
+        Self::new(raw)
+    }
+
+    /// Returns the number of elements a Vector can hold without
+    /// reallocating.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the Array isn't one-dimensional.
+    pub fn capacity(&self) -> Result<usize> {
+        if self.ndims()? != 1 {
+            return Err(Error::InvalidArgument(
+                "only vectors have a capacity".to_owned(),
+            ));
+        }
+        let raw = self.lock()?;
+        Ok(unsafe { (*raw).__bindgen_anon_1.maxsize })
+    }
+
+    /// Appends `x` to the end of a Vector, converting it to the element
+    /// type, using `push!`.
+    pub fn push(&self, x: &Value) -> Result<()> {
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        Function::base("push!")?.call2(&array, x)?;
+        Ok(())
+    }
+
+    /// Creates a `Matrix{Float64}` from row-major `rows`, storing the data
+    /// in Julia's column-major order.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the rows differ in length.
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self> {
+        let nrows = rows.len();
+        let ncols = rows.first().map_or(0, Vec::len);
+        if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != ncols) {
+            return Err(Error::InvalidArgument(format!(
+                "row {} has {} elements, expected {}",
+                i,
+                row.len(),
+                ncols
+            )));
+        }
+
+        let atype = unsafe { jl_apply_array_type(jl_float64_type as *mut jl_value_t, 2) };
+        jl_catch!();
+        let raw = unsafe { jl_alloc_array_2d(atype, nrows, ncols) };
+        jl_catch!();
+        let data =
+            unsafe { slice::from_raw_parts_mut(jl_array_data(raw) as *mut f64, nrows * ncols) };
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                data[j * nrows + i] = x;
+            }
+        }
+        Self::new(raw)
+    }
+
     /// Returns the length of the Array.
     pub fn len(&self) -> Result<usize> {
         let len = unsafe { jl_array_len(self.lock()?) };
@@ -38,13 +202,49 @@ impl Array {
         Ok(ndims)
     }
 
+    /// Returns the size of every dimension. Empty for a 0-dimensional
+    /// Array.
+    pub fn dims(&self) -> Result<Vec<usize>> {
+        (0..self.ndims()?).map(|i| self.dim(i)).collect()
+    }
+
+    /// Returns the element type of the Array.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the element type isn't a DataType, e.g.
+    /// for a `Vector{Union{Int, Nothing}}`.
+    pub fn element_type(&self) -> Result<Datatype> {
+        let raw = self.lock()?;
+        let eltype = unsafe { jl_tparam0(jl_typeof(raw) as *mut jl_value_t) };
+        if !unsafe { jl_is_datatype(eltype) } {
+            return Err(Error::InvalidUnbox);
+        }
+        Datatype::new(eltype as *mut jl_datatype_t)
+    }
+
     /// Checks if the Array is empty.
     pub fn is_empty(&self) -> bool {
         self.len().unwrap_or(0) == 0
     }
 
+    /// Checks if the elements are stored inline as a union of bits types,
+    /// e.g. for a `Vector{Union{Int, Nothing}}`. Such arrays store a type
+    /// selector byte per element after the data.
+    pub fn is_bits_union(&self) -> Result<bool> {
+        let p = unsafe { jl_array_isbitsunion(self.lock()?) };
+        Ok(p)
+    }
+
     /// Constructs a Vec of Values from the Array.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the Array is a bits union.
     pub fn as_vec(&self) -> Result<Vec<Value>> {
+        if self.is_bits_union()? {
+            return Err(Error::InvalidUnbox);
+        }
         let len = self.len()?;
         let ptr = unsafe { jl_array_data(self.lock()?) as *mut *mut jl_value_t };
         let slice = unsafe { slice::from_raw_parts(ptr, len) };
@@ -55,8 +255,68 @@ impl Array {
         Ok(vec)
     }
 
+    /// Copies every element into the start of `dst` in one go, returning the
+    /// number of elements copied. The element type must be the Julia type
+    /// corresponding to `T`, e.g. `Float64` for `f64`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the element type doesn't match `T` and
+    /// Error::InvalidArgument if `dst` is shorter than the Array.
+    pub fn copy_to_slice<T: ArrayElement>(&self, dst: &mut [T]) -> Result<usize> {
+        if self.element_type()?.lock()? != T::julia_type() {
+            return Err(Error::InvalidUnbox);
+        }
+
+        let raw = self.lock()?;
+        if unsafe { (*raw).elsize } as usize != mem::size_of::<T>() {
+            return Err(Error::InvalidUnbox);
+        }
+        let len = unsafe { jl_array_len(raw) };
+        if dst.len() < len {
+            return Err(Error::InvalidArgument(format!(
+                "destination holds {} elements, array has {}",
+                dst.len(),
+                len
+            )));
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(jl_array_data(raw) as *const T, dst.as_mut_ptr(), len);
+        }
+        Ok(len)
+    }
+
+    /// Creates a `Vector{UInt8}` holding a copy of `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ByteArray::from_slice(bytes)?.into_value()
+    }
+
+    /// Borrows the data of a `Vector{UInt8}` without copying it.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the Array isn't a `Vector{UInt8}`.
+    pub fn as_bytes(&self) -> Result<&[u8]> {
+        let raw = self.lock()?;
+        let eltype = unsafe { jl_tparam0(jl_typeof(raw) as *mut jl_value_t) };
+        if eltype != unsafe { jl_uint8_type } as *mut jl_value_t || self.ndims()? != 1 {
+            return Err(Error::InvalidUnbox);
+        }
+        let len = unsafe { jl_array_len(raw) };
+        let ptr = unsafe { jl_array_data(raw) as *const u8 };
+        Ok(unsafe { slice::from_raw_parts(ptr, len) })
+    }
+
     /// Returns the value at a specified index.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the Array is a bits union.
     pub fn index(&self, idx: usize) -> Result<Value> {
+        if self.is_bits_union()? {
+            return Err(Error::InvalidUnbox);
+        }
         let raw = unsafe { jl_array_ptr_ref(self.lock()?, idx) };
         Value::new(raw)
     }
@@ -68,6 +328,345 @@ impl Array {
         }
         Ok(())
     }
+
+    /// Returns a proxy for the only element of a 0-dimensional Array,
+    /// indexed without any index like `x[]`.
+    pub fn at0(&self) -> ArrayAccess<'_> {
+        ArrayAccess {
+            array: self,
+            idxs: smallvec![],
+        }
+    }
+
+    /// Returns a proxy for the element at `idx`.
+    pub fn at(&self, idx: usize) -> ArrayAccess<'_> {
+        ArrayAccess {
+            array: self,
+            idxs: smallvec![idx],
+        }
+    }
+
+    /// Returns a proxy for the element at row `row` and column `col` of a
+    /// matrix.
+    pub fn at2(&self, row: usize, col: usize) -> ArrayAccess<'_> {
+        ArrayAccess {
+            array: self,
+            idxs: smallvec![row, col],
+        }
+    }
+
+    /// Assigns `src` to every element in place, like `x .= src`. `src` may
+    /// be a scalar or an array with broadcast-compatible dimensions.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UnhandledException containing an
+    /// `Exception::DimensionMismatch` if the dimensions don't match.
+    pub fn broadcast_assign(&self, src: &Value) -> Result<()> {
+        let dest = Value::new(self.lock()? as *mut jl_value_t)?;
+        let identity = Module::new(unsafe { jl_base_module })?.global("identity")?;
+        Function::base("broadcast!")?.call3(&identity, &dest, src)?;
+        Ok(())
+    }
+
+    /// Returns the matrix product of two Arrays. Loads `LinearAlgebra`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UnhandledException containing an
+    /// `Exception::DimensionMismatch` if the dimensions don't match.
+    pub fn matmul(&self, other: &Array) -> Result<Self> {
+        linear_algebra()?;
+        let a = Value::new(self.lock()? as *mut jl_value_t)?;
+        let b = Value::new(other.lock()? as *mut jl_value_t)?;
+        let product = Function::base("*")?.call2(&a, &b)?;
+        Self::from_value(product)
+    }
+
+    /// Returns a transposed copy of a vector or matrix.
+    pub fn transpose(&self) -> Result<Self> {
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        let transposed = Function::base("permutedims")?.call1(&array)?;
+        Self::from_value(transposed)
+    }
+
+    /// Returns a copy with the dimensions reordered, dimension `i` of the
+    /// result being dimension `perm[i]` of this Array. Dimensions are
+    /// counted from 0.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if `perm` isn't a permutation of the
+    /// dimensions.
+    pub fn permutedims(&self, perm: &[usize]) -> Result<Self> {
+        let ndims = self.ndims()?;
+        let mut seen = vec![false; ndims];
+        let valid = perm.len() == ndims
+            && perm
+                .iter()
+                .all(|&d| d < ndims && !mem::replace(&mut seen[d], true));
+        if !valid {
+            return Err(Error::InvalidArgument(format!(
+                "{:?} isn't a permutation of {} dimensions",
+                perm, ndims
+            )));
+        }
+
+        let perm: Vec<_> = perm.iter().map(|&d| Value::from(d as i64 + 1)).collect();
+        let perm = Function::base("tuple")?.call(&perm)?;
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        let permuted = Function::base("permutedims")?.call2(&array, &perm)?;
+        Self::from_value(permuted)
+    }
+
+    /// Returns the inverse of a square matrix. Loads `LinearAlgebra`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UnhandledException containing an `Exception::Singular`
+    /// if the matrix is singular.
+    pub fn inv(&self) -> Result<Self> {
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        let inverse = linear_algebra()?.function("inv")?.call1(&array)?;
+        Self::from_value(inverse)
+    }
+
+    /// Returns the determinant of a square matrix. Loads `LinearAlgebra`.
+    pub fn det(&self) -> Result<Value> {
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        linear_algebra()?.function("det")?.call1(&array)
+    }
+
+    /// Returns a sorted copy of the Array, leaving the original untouched.
+    /// Sorts in descending order if `rev` is set.
+    pub fn sort(&self, rev: bool) -> Result<Self> {
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        let rev = Value::from(rev);
+        let sorted = Function::base("sort")?.call_kw(&[array], &[("rev", &rev)])?;
+        Self::from_value(sorted)
+    }
+
+    /// Returns a copy of the Array sorted by the result of calling `keyfn` on
+    /// each element, leaving the original untouched.
+    pub fn sort_by(&self, keyfn: &Function) -> Result<Self> {
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        let keyfn = Value::new(keyfn.lock()? as *mut jl_value_t)?;
+        let sorted = Function::base("sort")?.call_kw(&[array], &[("by", &keyfn)])?;
+        Self::from_value(sorted)
+    }
+
+    /// Returns a view of the elements in `range`, sharing data with the
+    /// Array. Mutations through the view write through to the Array.
+    ///
+    /// The view is a `SubArray`, which doesn't share the memory layout of a
+    /// dense Array, so it's returned as a Value to be used through Julia
+    /// functions. Use `copy` on it to get a dense Array.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if `range` is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> Result<Value> {
+        let len = self.len()?;
+        if range.start > range.end || range.end > len {
+            return Err(Error::InvalidArgument(format!(
+                "range {:?} out of bounds for length {}",
+                range, len
+            )));
+        }
+
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        let start = Value::from(range.start as i64 + 1);
+        let stop = Value::from(range.end as i64);
+        let range = Function::base(":")?.call2(&start, &stop)?;
+        Function::base("view")?.call2(&array, &range)
+    }
+
+    /// Returns a view of the Array's data as elements of type `elty`, using
+    /// `reinterpret`, e.g. to read a `Vector{UInt8}` as a
+    /// `Vector{Float64}`. The view shares data with the Array.
+    ///
+    /// Like `slice`, the view is returned as a Value since a
+    /// `ReinterpretArray` doesn't share the memory layout of a dense Array.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if either element type isn't a bits
+    /// type. If the sizes don't fit, e.g. 12 bytes as Float64, Julia's
+    /// ArgumentError is returned.
+    pub fn reinterpret(&self, elty: &Datatype) -> Result<Value> {
+        let old = self.element_type()?;
+        for ty in [&old, elty] {
+            if !unsafe { jl_isbits(ty.lock()?) } {
+                return Err(Error::InvalidArgument(format!(
+                    "{} is not a bits type",
+                    Value::new(ty.lock()? as *mut jl_value_t)?
+                )));
+            }
+        }
+
+        let array = Value::new(self.lock()? as *mut jl_value_t)?;
+        let elty = Value::new(elty.lock()? as *mut jl_value_t)?;
+        Function::base("reinterpret")?.call2(&elty, &array)
+    }
+}
+
+/// Iterator over the elements of an Array in linear order, created by
+/// `Array::into_iter`. Elements of bits types are boxed, undefined elements
+/// are returned as errors.
+///
+/// The Array is kept in a vector which is rooted until the iterator is
+/// dropped, so several iterators over the same Array don't interfere. If
+/// the handle isn't an Array or rooting fails, the iterator only yields
+/// that error.
+pub struct ArrayIntoIter {
+    array: Array,
+    // Any[array]
+    holder: Option<Value>,
+    err: Option<Error>,
+    idx: usize,
+    len: usize,
+}
+
+impl ArrayIntoIter {
+    fn new(array: Array) -> Self {
+        let root = || -> Result<(Value, usize)> {
+            let value = Value::new(array.lock()? as *mut jl_value_t)?;
+            if !value.is_array() {
+                return Err(Error::InvalidUnbox);
+            }
+            let raw = unsafe { jl_alloc_vec_any(1) };
+            jl_catch!();
+            let holder = Array::new(raw)?;
+            holder.index_set(0, &value)?;
+            let holder = Value::new(raw as *mut jl_value_t)?;
+            super::iter::root(&holder)?;
+            Ok((holder, array.len()?))
+        };
+        let (holder, err, len) = match root() {
+            Ok((holder, len)) => (Some(holder), None, len),
+            Err(err) => (None, Some(err), 0),
+        };
+        Self {
+            array,
+            holder,
+            err,
+            idx: 0,
+            len,
+        }
+    }
+
+    fn elem(&self, idx: usize) -> Result<Value> {
+        let raw = unsafe { jl_arrayref(self.array.lock()?, idx) };
+        jl_catch!();
+        Value::new(raw)
+    }
+}
+
+impl Iterator for ArrayIntoIter {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.err.take() {
+            return Some(Err(err));
+        }
+        if self.idx >= self.len {
+            return None;
+        }
+        let elem = self.elem(self.idx);
+        self.idx += 1;
+        Some(elem)
+    }
+}
+
+impl Drop for ArrayIntoIter {
+    fn drop(&mut self) {
+        if let Some(ref holder) = self.holder {
+            let _ = super::iter::unroot(holder);
+        }
+    }
+}
+
+/// Consumes the handle, yielding every element as a Value.
+impl IntoIterator for Array {
+    type Item = Result<Value>;
+    type IntoIter = ArrayIntoIter;
+
+    fn into_iter(self) -> ArrayIntoIter {
+        ArrayIntoIter::new(self)
+    }
+}
+
+/// Proxy for a single element of an Array, created by `Array::at` and
+/// `Array::at2`.
+///
+/// Indices are 0-based and translated to Julia's 1-based indices internally.
+/// Accesses are bounds-checked by Julia and fail with Error::BoundsError.
+pub struct ArrayAccess<'a> {
+    array: &'a Array,
+    idxs: SmallVec<[usize; 2]>,
+}
+
+impl Array {
+    /// Returns the element type, dimensions and the first few elements,
+    /// formatted for `Debug`.
+    fn debug_summary(&self) -> Result<(String, Vec<usize>, Vec<String>)> {
+        let raw = self.lock()?;
+        let eltype = unsafe { jl_tparam0(jl_typeof(raw) as *mut jl_value_t) };
+        let eltype = Value::new(eltype)?.to_string();
+        let dims = self.dims()?;
+        let preview = (0..self.len()?.min(DEBUG_PREVIEW_LEN))
+            .map(|i| self.at(i).get().map(|x| x.to_string()))
+            .collect::<Result<_>>()?;
+        Ok((eltype, dims, preview))
+    }
+}
+
+impl fmt::Debug for Array {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (eltype, dims, preview) = match self.debug_summary() {
+            Ok(summary) => summary,
+            Err(_) => return write!(f, "Array(?)"),
+        };
+        let dims: Vec<_> = dims.iter().map(usize::to_string).collect();
+        write!(
+            f,
+            "Array{{{}, {}}}({})[{}",
+            eltype,
+            dims.len(),
+            dims.join(", "),
+            preview.join(", ")
+        )?;
+        if self.len().unwrap_or(0) > preview.len() {
+            write!(f, ", …")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl ArrayAccess<'_> {
+    fn args(&self) -> Result<Vec<Value>> {
+        let mut args = vec![Value::new(self.array.lock()? as *mut jl_value_t)?];
+        args.extend(self.idxs.iter().map(|&i| Value::from(i as i64 + 1)));
+        Ok(args)
+    }
+
+    /// Returns the element.
+    pub fn get(&self) -> Result<Value> {
+        let args = self.args()?;
+        Function::base("getindex")?
+            .call(&args)
+            .map_err(Error::classify)
+    }
+
+    /// Sets the element.
+    pub fn set(&self, x: &Value) -> Result<()> {
+        let mut args = self.args()?;
+        args.insert(1, x.clone());
+        Function::base("setindex!")?
+            .call(&args)
+            .map_err(Error::classify)?;
+        Ok(())
+    }
 }
 
 impl ByteArray {
@@ -82,10 +681,31 @@ impl ByteArray {
         self.len().unwrap_or(0) == 0
     }
 
+    /// Creates a `Vector{UInt8}` holding a copy of `bytes`.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let atype = unsafe { jl_apply_array_type(jl_uint8_type as *mut jl_value_t, 1) };
+        jl_catch!();
+        let raw = unsafe { jl_alloc_array_1d(atype, bytes.len()) };
+        jl_catch!();
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), jl_array_data(raw) as *mut u8, bytes.len());
+        }
+        Self::new(raw)
+    }
+
     /// Constructs a slice of bytes without allocating new space.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the array is a bits union, whose data
+    /// isn't a plain sequence of bytes.
     pub fn as_slice(&self) -> Result<&[u8]> {
         let len = self.len()?;
-        let ptr = unsafe { jl_array_data(self.lock()?) as *mut u8 };
+        let raw = self.lock()?;
+        if unsafe { jl_array_isbitsunion(raw) } {
+            return Err(Error::InvalidUnbox);
+        }
+        let ptr = unsafe { jl_array_data(raw) as *mut u8 };
         let slice = unsafe { slice::from_raw_parts(ptr, len) };
         Ok(slice)
     }
@@ -111,6 +731,35 @@ impl ByteArray {
 }
 
 impl Svec {
+    /// Constructs a Svec containing `values`. Returns the shared empty Svec
+    /// if `values` is empty.
+    pub fn from_values(values: &[&Value]) -> Result<Self> {
+        if values.is_empty() {
+            return Self::new(unsafe { jl_emptysvec });
+        }
+
+        let mut elems = Vec::with_capacity(values.len());
+        for v in values {
+            elems.push(v.lock()?);
+        }
+
+        // the elements aren't rooted, so keep the GC from running while the
+        // Svec is allocated
+        let raw = unsafe {
+            let enabled = jl_gc_enable(0);
+            let raw = jl_alloc_svec(elems.len());
+            if !raw.is_null() {
+                for (i, elem) in elems.into_iter().enumerate() {
+                    jl_svecset(raw, i, elem);
+                }
+            }
+            jl_gc_enable(enabled);
+            raw
+        };
+        jl_catch!();
+        Self::new(raw)
+    }
+
     /// Returns the length of the Svec.
     pub fn len(&self) -> Result<usize> {
         let len = unsafe { jl_svec_len(self.lock()?) };
@@ -1,10 +1,13 @@
 //! Module providing wrappers for the native Julia type-types.
 
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::mem;
 use std::ptr;
 use std::result;
+use std::sync::PoisonError;
 
-use crate::api::{Array, IntoSymbol, JlValue, Svec, Value};
+use crate::api::{Array, IntoSymbol, JlValue, Module, Svec, Symbol, Value};
 use crate::error::{Error, Result};
 use crate::jlvalues;
 use crate::sys::*;
@@ -201,16 +204,145 @@ impl Datatype {
     }
 
     /// Creates a new Julia primitive of this type.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the type isn't a bits type or if the
+    /// length of `data` doesn't match the size of the type.
     pub fn new_bits<T: Into<Vec<u8>>>(&self, data: T) -> Result<Value> {
         let data = data.into();
         let bits = data.as_ptr();
 
         let dt = self.lock()?;
+        if !unsafe { jl_isbits(dt) } || data.len() != unsafe { jl_datatype_size(dt) } {
+            return Err(Error::InvalidUnbox);
+        }
+        let value = unsafe { jl_new_bits(dt as *mut _, bits as *mut _) };
+        jl_catch!();
+        Value::new(value)
+    }
+
+    /// Boxes a Rust value as a Julia primitive or bits struct of this type,
+    /// copying its bytes.
+    ///
+    /// `T` has to have the same layout as the Julia type: the same size and
+    /// alignment, and every field at the same offset with a matching type.
+    /// A `#[repr(C)]` struct whose fields mirror the Julia fields in order
+    /// does, a plain Rust struct may reorder its fields. Only size and
+    /// alignment are checked, fields aren't.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the type isn't a bits type or if its
+    /// size or alignment differ from `T`'s.
+    pub fn box_bits<T: Copy>(&self, value: T) -> Result<Value> {
+        let dt = self.lock()?;
+        if !unsafe { jl_isbits(dt) } {
+            return Err(Error::InvalidUnbox);
+        }
+        let size = unsafe { jl_datatype_size(dt) };
+        let align = unsafe { jl_datatype_align(dt) };
+        if size != mem::size_of::<T>() || align != mem::align_of::<T>() {
+            return Err(Error::InvalidUnbox);
+        }
+
+        let bits = &value as *const T;
         let value = unsafe { jl_new_bits(dt as *mut _, bits as *mut _) };
         jl_catch!();
         Value::new(value)
     }
 
+    /// Returns the bare name of the type, without parameters, e.g.
+    /// `Vector` for `Vector{Int}`.
+    pub fn name(&self) -> Result<String> {
+        let name = unsafe { (*(*self.lock()?).name).name };
+        String::try_from(&Symbol::new(name)?)
+    }
+
+    /// Returns the name of the type qualified with the module defining it,
+    /// e.g. `Base.Dict`.
+    pub fn qualified_name(&self) -> Result<String> {
+        let module = unsafe { (*(*self.lock()?).name).module };
+        let module = Module::new(module)?.fullname()?;
+        Ok(format!("{}.{}", module, self.name()?))
+    }
+
+    /// Returns the signatures of the constructors of this type, as listed by
+    /// `methods(T)`.
+    pub fn constructors(&self) -> Result<Vec<String>> {
+        let base = Module::new(unsafe { jl_base_module })?;
+        let dt = Value::new(self.lock()? as *mut jl_value_t)?;
+        let methods = base.function("methods")?.call1(&dt)?;
+        let string = base.global("string")?;
+        let sigs = base.function("map")?.call2(&string, &methods)?;
+        Array::from_value(sigs)?
+            .as_vec()?
+            .iter()
+            .map(String::try_from)
+            .collect()
+    }
+
+    /// Locks the datatype, checking that it has a computed layout with a
+    /// field `i`.
+    fn layout_field(&self, i: usize) -> Result<*mut jl_datatype_t> {
+        let dt = self.lock()?;
+        if unsafe { (*dt).layout.is_null() } {
+            return Err(Error::InvalidArgument(
+                "datatype has no computed layout".to_owned(),
+            ));
+        }
+        let nfields = unsafe { jl_datatype_nfields(dt) };
+        if i >= nfields {
+            return Err(Error::InvalidArgument(format!(
+                "field index {} out of range for {} fields",
+                i, nfields
+            )));
+        }
+        Ok(dt)
+    }
+
+    /// Returns the byte offset of the `i`th field, counting from 0.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the index is out of range or the
+    /// type has no layout, e.g. because it's abstract.
+    pub fn field_offset(&self, i: usize) -> Result<usize> {
+        let dt = self.layout_field(i)?;
+        Ok(unsafe { jl_field_offset(dt, i) })
+    }
+
+    /// Checks if the `i`th field, counting from 0, is stored as a pointer
+    /// to a boxed value rather than inline.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the index is out of range or the
+    /// type has no layout, e.g. because it's abstract.
+    pub fn field_is_pointer(&self, i: usize) -> Result<bool> {
+        let dt = self.layout_field(i)?;
+        Ok(unsafe { jl_field_isptr(dt, i as isize) })
+    }
+
+    /// Checks if the datatype is mutable.
+    pub fn is_mutable(&self) -> bool {
+        self.lock()
+            .map(|dt| unsafe { jl_is_mutable(dt) })
+            .unwrap_or(false)
+    }
+    /// Checks if the datatype is abstract.
+    pub fn is_abstract(&self) -> bool {
+        self.lock()
+            .map(|dt| unsafe { jl_is_abstracttype(dt) })
+            .unwrap_or(false)
+    }
+    /// Checks if the datatype is concrete, i.e. can have instances.
+    pub fn is_concrete(&self) -> bool {
+        self.lock()
+            .map(|dt| unsafe { jl_is_concrete_type(dt as *const _) })
+            .unwrap_or(false)
+    }
+
     pub fn any() -> Self {
         unsafe { Self::new_unchecked(jl_any_type) }
     }
@@ -330,6 +462,47 @@ impl UnionAll {
         jl_catch!();
         Type::new(raw)
     }
+
+    /// Returns the type variable bound by this UnionAll, e.g. `T` in
+    /// `Vector{T} where T`.
+    pub fn var(&self) -> Result<Value> {
+        let var = unsafe { (*self.lock()?).var };
+        Value::new(var as *mut jl_value_t)
+    }
+
+    /// Returns the body of this UnionAll, i.e. the type with the type
+    /// variable unbound. The body may itself be a UnionAll.
+    pub fn body(&self) -> Result<Type> {
+        let body = unsafe { (*self.lock()?).body };
+        Type::new(body)
+    }
+}
+
+impl Datatype {
+    /// Returns the pointer to the datatype, even if the lock is poisoned.
+    fn as_ptr(&self) -> *mut jl_datatype_t {
+        self._inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ptr()
+    }
+}
+
+// Julia caches every DataType it creates, e.g. `Vector{Int}` always refers
+// to the same object, so two datatypes are equal iff they are the same
+// object.
+impl PartialEq for Datatype {
+    fn eq(&self, other: &Datatype) -> bool {
+        self.as_ptr() == other.as_ptr()
+    }
+}
+
+impl Eq for Datatype {}
+
+impl Hash for Datatype {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ptr().hash(state);
+    }
 }
 
 impl Tuple {
@@ -345,6 +518,7 @@ impl Tuple {
 /// Type for constructing new primitive, abstract or compound types.
 pub struct TypeBuilder {
     name: *mut jl_sym_t,
+    module: *mut jl_module_t,
     supertype: *mut jl_datatype_t,
     params: *mut jl_svec_t,
     fnames: *mut jl_svec_t,
@@ -362,6 +536,7 @@ impl TypeBuilder {
     pub fn new() -> Self {
         Self {
             name: ptr::null_mut(),
+            module: unsafe { jl_main_module },
             supertype: unsafe { jl_any_type },
             params: unsafe { jl_emptysvec },
             fnames: unsafe { jl_emptysvec },
@@ -385,35 +560,72 @@ impl TypeBuilder {
         self.err.is_some()
     }
 
-    // /// Builds the Type. If any errors occurred previously, they will be returned here.
-    // pub fn build(self) -> Result<Datatype> {
-    //     if let Some(err) = self.err {
-    //         return Err(err);
-    //     }
-
-    //     if self.primitive {
-    //         let raw = unsafe {
-    //             jl_new_primitivetype(self.name as *mut _, self.supertype, self.params, self.nbits)
-    //         };
-    //         jl_catch!();
-    //         Datatype::new(raw)
-    //     } else {
-    //         let raw = unsafe {
-    //             jl_new_datatype(
-    //                 self.name,
-    //                 self.supertype,
-    //                 self.params,
-    //                 self.fnames,
-    //                 self.ftypes,
-    //                 self.abstrac as i32,
-    //                 self.mutable as i32,
-    //                 self.ninitialized as i32,
-    //             )
-    //         };
-    //         jl_catch!();
-    //         Datatype::new(raw)
-    //     }
-    // }
+    /// Builds the Type. If any errors occurred previously, they will be
+    /// returned here.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if no name was set or if the number of
+    /// field names and field types differ.
+    pub fn build(self) -> Result<Datatype> {
+        if let Some(err) = self.err {
+            return Err(err);
+        }
+        if self.name.is_null() {
+            return Err(Error::InvalidArgument("the type has no name".to_owned()));
+        }
+
+        let raw = if self.primitive {
+            unsafe {
+                jl_new_primitivetype(
+                    self.name as *mut _,
+                    self.module,
+                    self.supertype,
+                    self.params,
+                    self.nbits,
+                )
+            }
+        } else {
+            // Julia reads both svecs with the length of fnames
+            let nfnames = unsafe { jl_svec_len(self.fnames) };
+            let nftypes = unsafe { jl_svec_len(self.ftypes) };
+            if nfnames != nftypes {
+                return Err(Error::InvalidArgument(format!(
+                    "{} field names but {} field types",
+                    nfnames, nftypes
+                )));
+            }
+
+            unsafe {
+                jl_new_datatype(
+                    self.name,
+                    self.module,
+                    self.supertype,
+                    self.params,
+                    self.fnames,
+                    self.ftypes,
+                    jl_emptysvec,
+                    self.abstrac as i32,
+                    self.mutable as i32,
+                    self.ninitialized as i32,
+                )
+            }
+        };
+        jl_catch!();
+        Datatype::new(raw)
+    }
+
+    /// Sets the module the type belongs to. Defaults to Main.
+    pub fn module(mut self, module: &Module) -> Self {
+        self.module = match module.lock() {
+            Ok(module) => module,
+            Err(err) => {
+                self.err = Some(err);
+                return self;
+            }
+        };
+        self
+    }
 
     /// Sets the name.
     pub fn name<S: IntoSymbol>(mut self, name: S) -> Self {
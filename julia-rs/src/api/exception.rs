@@ -1,16 +1,83 @@
 //! Module providing wrappers for the native Julia exceptions.
 
+use std::convert::TryFrom;
+use std::ffi::c_void;
 use std::fmt;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
 use smallvec::SmallVec;
 
-use super::{Datatype, JlValue, Symbol, Value};
+use super::{Datatype, Function, JlValue, Module, Symbol, Value};
 use crate::error::Result;
 use crate::string::IntoCString;
 use crate::sys::*;
 
+/// Runs `f` with the pending exception cleared, restoring it afterwards.
+/// An exception left behind by an earlier, unrelated operation thus can't be
+/// mistaken for one thrown by `f`, and it's still there for whoever checks
+/// for it later. `f` is expected to catch its own exceptions.
+pub(crate) fn isolated<T, F: FnOnce() -> T>(f: F) -> T {
+    unsafe {
+        let previous = jl_exception_occurred();
+        if previous.is_null() {
+            return f();
+        }
+
+        // keep the previous exception rooted while it's out of the slot
+        let mut frame: [*mut c_void; 3] = [
+            (1 << 2) as *mut c_void,
+            jl_pgcstack() as *mut c_void,
+            previous as *mut c_void,
+        ];
+        jl_set_pgcstack(frame.as_mut_ptr() as *mut jl_gcframe_t);
+        jl_exception_clear();
+
+        let ret = f();
+
+        jl_set_pgcstack(frame[1] as *mut jl_gcframe_t);
+        if jl_exception_occurred().is_null() {
+            (*(*jl_current_task()).ptls).previous_exception = previous;
+        }
+        ret
+    }
+}
+
+/// Fields of a BoundsError, returned by `Exception::as_bounds`.
+#[derive(Debug, Clone)]
+pub struct BoundsInfo {
+    /// The indexed collection, if the error recorded it.
+    pub array: Option<Value>,
+    /// The offending index, or a tuple of indices, if the error recorded it.
+    pub index: Option<Value>,
+}
+
+/// Fields of a MethodError, returned by `Exception::as_method`.
+#[derive(Debug, Clone)]
+pub struct MethodInfo {
+    /// The function that was called.
+    pub function: Value,
+    /// The types of the arguments it was called with.
+    pub arg_types: Vec<Value>,
+}
+
+/// Fields of a TypeError, returned by `Exception::as_type`.
+#[derive(Debug, Clone)]
+pub struct TypeErrorInfo {
+    /// The name of the function or construct which failed, e.g. `typeassert`.
+    pub func: Symbol,
+    /// The type which was expected.
+    pub expected: Value,
+    /// The value which was received instead.
+    pub got: Value,
+}
+
+/// Returns the type of `value` as a Value.
+fn type_of(value: &Value) -> Result<Value> {
+    let ty = unsafe { jl_typeof(value.lock()?) };
+    Value::new(ty as *mut jl_value_t)
+}
+
 /// Enum containing different Julia exceptions wrapped as a Value.
 #[derive(Debug, Clone)]
 pub enum Exception {
@@ -26,6 +93,8 @@ pub enum Exception {
     Domain(Value),
     /// No more data is available from file or stream
     EOF(Value),
+    /// The dimensions of the arguments don't match
+    DimensionMismatch(Value),
     /// Generic error occurred
     Error(Value),
     /// Type conversion cannot be done exactly
@@ -52,6 +121,8 @@ pub enum Exception {
     Overflow(Value),
     /// The expression couldn't be parsed as a valid Julia expression
     Parse(Value),
+    /// The matrix is singular and can't be inverted
+    Singular(Value),
     /// System call failed
     System(Value),
     /// Type assertion failed
@@ -92,6 +163,7 @@ impl Exception {
             "CompositeException" => Self::Composite(value),
             "DivideError" => Self::Divide(value),
             "DomainError" => Self::Domain(value),
+            "DimensionMismatch" => Self::DimensionMismatch(value),
             "EOFError" => Self::EOF(value),
             "ErrorException" => Self::Error(value),
             "InexactError" => Self::Inexact(value),
@@ -106,6 +178,7 @@ impl Exception {
             "MethodError" => Self::Method(value),
             "OverflowError" => Self::Overflow(value),
             "ParseError" => Self::Parse(value),
+            "SingularException" => Self::Singular(value),
             "SystemError" => Self::System(value),
             "TypeError" => Self::Type(value),
             "UndefRefError" => Self::UndefRef(value),
@@ -116,6 +189,82 @@ impl Exception {
         Ok(ex)
     }
 
+    /// Returns the message Julia would print for the exception, as produced
+    /// by `showerror`.
+    pub fn message(&self) -> Result<String> {
+        let showerror = Module::new(unsafe { jl_base_module })?.global("showerror")?;
+        let msg = Function::base("sprint")?.call2(&showerror, self.inner_ref())?;
+        String::try_from(&msg)
+    }
+
+    /// Returns the fields of a BoundsError, None for any other exception.
+    pub fn as_bounds(&self) -> Option<BoundsInfo> {
+        match *self {
+            // either field may be undefined, e.g. for `BoundsError()`
+            Self::Bounds(ref ex) => Some(BoundsInfo {
+                array: ex.get("a").ok(),
+                index: ex.get("i").ok(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the function and the argument types of a MethodError, None
+    /// for any other exception or if the fields can't be read.
+    pub fn as_method(&self) -> Option<MethodInfo> {
+        let ex = match *self {
+            Self::Method(ref ex) => ex,
+            _ => return None,
+        };
+        let info = || -> Result<MethodInfo> {
+            let arg_types = ex
+                .get("args")?
+                .tuple_fields()?
+                .iter()
+                .map(type_of)
+                .collect::<Result<_>>()?;
+            Ok(MethodInfo {
+                function: ex.get("f")?,
+                arg_types,
+            })
+        };
+        info().ok()
+    }
+
+    /// Returns the expected type and the received value of a TypeError,
+    /// None for any other exception or if the fields can't be read.
+    pub fn as_type(&self) -> Option<TypeErrorInfo> {
+        let ex = match *self {
+            Self::Type(ref ex) => ex,
+            _ => return None,
+        };
+        let info = || -> Result<TypeErrorInfo> {
+            Ok(TypeErrorInfo {
+                func: Symbol::from_value(ex.get("func")?)?,
+                expected: ex.get("expected")?,
+                got: ex.get("got")?,
+            })
+        };
+        info().ok()
+    }
+
+    /// Throws the exception back into Julia, preserving its type. Once thrown,
+    /// Julia keeps the value alive until it's caught.
+    ///
+    /// # Safety
+    /// Julia unwinds to the nearest Julia exception handler with a longjmp,
+    /// skipping Rust destructors in between. Only call this from Rust code
+    /// called by Julia, e.g. through `ccall`, with nothing left to drop.
+    pub unsafe fn rethrow(self) -> ! {
+        // the longjmp skips destructors, so release the handle before it
+        let raw = self.inner_ref().lock();
+        drop(self);
+        match raw {
+            Ok(raw) => jl_throw(raw),
+            Err(_) => jl_error(c"exception value is poisoned".as_ptr()),
+        }
+    }
+
     /// Immutably borrows the inner value.
     pub const fn inner_ref(&self) -> &Value {
         match *self {
@@ -125,6 +274,7 @@ impl Exception {
             Self::Divide(ref value) => value,
             Self::Domain(ref value) => value,
             Self::EOF(ref value) => value,
+            Self::DimensionMismatch(ref value) => value,
             Self::Error(ref value) => value,
             Self::Inexact(ref value) => value,
             Self::Init(ref value) => value,
@@ -138,6 +288,7 @@ impl Exception {
             Self::Method(ref value) => value,
             Self::Overflow(ref value) => value,
             Self::Parse(ref value) => value,
+            Self::Singular(ref value) => value,
             Self::System(ref value) => value,
             Self::Type(ref value) => value,
             Self::UndefRef(ref value) => value,
@@ -156,6 +307,7 @@ impl Exception {
             Self::Divide(ref mut value) => value,
             Self::Domain(ref mut value) => value,
             Self::EOF(ref mut value) => value,
+            Self::DimensionMismatch(ref mut value) => value,
             Self::Error(ref mut value) => value,
             Self::Inexact(ref mut value) => value,
             Self::Init(ref mut value) => value,
@@ -169,6 +321,7 @@ impl Exception {
             Self::Method(ref mut value) => value,
             Self::Overflow(ref mut value) => value,
             Self::Parse(ref mut value) => value,
+            Self::Singular(ref mut value) => value,
             Self::System(ref mut value) => value,
             Self::Type(ref mut value) => value,
             Self::UndefRef(ref mut value) => value,
@@ -187,6 +340,7 @@ impl Exception {
             Self::Divide(value) => value,
             Self::Domain(value) => value,
             Self::EOF(value) => value,
+            Self::DimensionMismatch(value) => value,
             Self::Error(value) => value,
             Self::Inexact(value) => value,
             Self::Init(value) => value,
@@ -200,6 +354,7 @@ impl Exception {
             Self::Method(value) => value,
             Self::Overflow(value) => value,
             Self::Parse(value) => value,
+            Self::Singular(value) => value,
             Self::System(value) => value,
             Self::Type(value) => value,
             Self::UndefRef(value) => value,
@@ -223,9 +378,14 @@ impl DerefMut for Exception {
     }
 }
 
-// TODO
 impl fmt::Display for Exception {
+    /// Writes the message Julia would print for the exception, or a short
+    /// description of the kind of exception if that fails.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Ok(msg) = self.message() {
+            return f.write_str(&msg);
+        }
+
         let description = match *self {
             Self::Argument(_) => "the parameters to a function call do not match a valid signature",
             Self::Bounds(_) => "attempt to access index out-of-bounds",
@@ -233,6 +393,7 @@ impl fmt::Display for Exception {
             Self::Divide(_) => "divide by zero",
             Self::Domain(_) => "the argument is outside of the valid domain",
             Self::EOF(_) => "no more data is available from file or stream",
+            Self::DimensionMismatch(_) => "the dimensions of the arguments don't match",
             Self::Error(_) => "generic error occurred",
             Self::Inexact(_) => "type conversion cannot be done exactly",
             Self::Init(_) => "an error occurred when running a module's __init__ ",
@@ -246,6 +407,7 @@ impl fmt::Display for Exception {
             Self::Method(_) => "method with the required type signature doesn't exist",
             Self::Overflow(_) => "the result of an expression is too large",
             Self::Parse(_) => "the expression couldn't be parsed as a valid Julia expression",
+            Self::Singular(_) => "the matrix is singular and can't be inverted",
             Self::System(_) => "system call failed",
             Self::Type(_) => "type assertion failed",
             Self::UndefRef(_) => "the item or field is not defined",
@@ -257,6 +419,8 @@ impl fmt::Display for Exception {
     }
 }
 
+impl std::error::Error for Exception {}
+
 /// Throws a generic error.
 pub fn error<S: IntoCString>(string: S) {
     let string = string.into_cstring();
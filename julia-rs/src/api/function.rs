@@ -1,8 +1,14 @@
 //! Module providing a wrapper for the native Julia function object.
 
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use std::time::Duration;
+
 use smallvec::SmallVec;
 
-use super::{JlValue, Value};
+use super::datatype::Type;
+use super::exception;
+use super::{IntoSymbol, JlValue, Module, Symbol, Value};
 use crate::error::{Error, Result};
 use crate::{jlvalues, sys::*};
 
@@ -10,8 +16,157 @@ jlvalues! {
     pub struct Function(jl_function_t);
 }
 
+/// The different kinds of callable values.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum FunctionKind {
+    /// A named generic function, with methods that can be inspected and
+    /// extended.
+    Generic,
+    /// An anonymous function or a function capturing variables.
+    Closure,
+    /// A builtin function implemented in C, such as `getfield`.
+    Builtin,
+    /// An intrinsic, such as `Core.Intrinsics.add_int`.
+    Intrinsic,
+    /// A value that isn't a function, such as a type or a callable struct.
+    Other,
+}
+
+/// Calls `f` with `args` while the arguments are rooted in a GC frame, like
+/// `JL_GC_PUSHARGS` does for C embedders.
+///
+/// # Safety
+/// `f` and every argument must be valid Julia values.
+unsafe fn call_rooted(f: *mut jl_function_t, args: &[*mut jl_value_t]) -> *mut jl_value_t {
+    // [nroots << 2, prev, roots...], the roots are stored inline
+    let mut frame = SmallVec::<[*mut c_void; 10]>::with_capacity(args.len() + 2);
+    frame.push((args.len() << 2) as *mut c_void);
+    frame.push(jl_pgcstack() as *mut c_void);
+    frame.extend(args.iter().map(|&arg| arg as *mut c_void));
+
+    let frame = frame.as_mut_ptr();
+    jl_set_pgcstack(frame as *mut jl_gcframe_t);
+    // jl_call catches exceptions, so the frame is always popped
+    let ret = jl_call(f, frame.add(2) as *mut *mut jl_value_t, args.len() as u32);
+    jl_set_pgcstack((*(frame as *mut jl_gcframe_t)).prev);
+    ret
+}
+
+/// Trait implemented by tuples of up to 8 values convertible into Values,
+/// which `Function::call_with` boxes into arguments.
+pub trait IntoValues {
+    fn into_values(self) -> SmallVec<[Value; 8]>;
+}
+
+macro_rules! into_values_tuple {
+    ($($t:ident),*) => {
+        impl<$($t: Into<Value>),*> IntoValues for ($($t,)*) {
+            #[allow(non_snake_case)]
+            fn into_values(self) -> SmallVec<[Value; 8]> {
+                let ($($t,)*) = self;
+                SmallVec::from_iter([$($t.into()),*])
+            }
+        }
+    };
+}
+
+into_values_tuple!();
+into_values_tuple!(A);
+into_values_tuple!(A, B);
+into_values_tuple!(A, B, C);
+into_values_tuple!(A, B, C, D);
+into_values_tuple!(A, B, C, D, E);
+into_values_tuple!(A, B, C, D, E, F);
+into_values_tuple!(A, B, C, D, E, F, G);
+into_values_tuple!(A, B, C, D, E, F, G, H);
+
+/// Julia function which returns a closure calling `f` with `bound`
+/// prepended to its arguments.
+fn partial_fn() -> Result<Function> {
+    super::cached_function(
+        "partial",
+        "(f, bound...) -> (args...; kwargs...) -> f(bound..., args...; kwargs...)",
+    )
+}
+
+/// Julia function which calls `f` on a Task and interrupts it if it takes
+/// longer than `secs`. Returns `(true, result)` or `(false, nothing)` if it
+/// timed out.
+fn timeout_fn() -> Result<Function> {
+    super::cached_function(
+        "timeout",
+        r#"(f, secs, args...) -> begin
+            task = @async f(args...)
+            timedout = Ref(false)
+            timer = Timer(secs) do _
+                if !istaskdone(task)
+                    timedout[] = true
+                    schedule(task, InterruptException(); error=true)
+                end
+            end
+            try
+                (true, fetch(task))
+            catch e
+                timedout[] && return (false, nothing)
+                throw(e isa TaskFailedException ? e.task.exception : e)
+            finally
+                close(timer)
+            end
+        end"#,
+    )
+}
+
+/// Julia function which calls `f` using `@timed`, measuring the elapsed time
+/// and the allocated bytes from the GC counters.
+fn timed_fn() -> Result<Function> {
+    super::cached_function("timed", "(f, args...) -> @timed f(args...)")
+}
+
 impl Function {
-    /// Call with a sequence of Value-s.
+    /// Returns the function bound to `name` in the base module.
+    pub(crate) fn base<S: IntoSymbol>(name: S) -> Result<Self> {
+        Module::new(unsafe { jl_base_module })?.function(name)
+    }
+
+    /// Returns the name of the function. Anonymous functions have generated
+    /// names like `#1`.
+    pub fn name(&self) -> Result<Symbol> {
+        let f = self.lock()?;
+        let mt = unsafe { jl_gf_mtable(f) };
+        if mt.is_null() {
+            return Err(Error::NotAFunction);
+        }
+        Symbol::new(unsafe { jl_gf_name(f) })
+    }
+
+    /// Returns what kind of callable the Function is.
+    pub fn kind(&self) -> Result<FunctionKind> {
+        let f = self.lock()?;
+        let kind = unsafe {
+            if jl_is_intrinsic(f) {
+                FunctionKind::Intrinsic
+            } else if jl_isa(f, jl_builtin_type as *mut _) != 0 {
+                FunctionKind::Builtin
+            } else if jl_isa(f, jl_function_type as *mut _) == 0 {
+                FunctionKind::Other
+            } else {
+                let dt = jl_typeof(f) as *const jl_datatype_t;
+                let mt = (*(*dt).name).mt;
+                // anonymous functions have names like `#1`
+                let anonymous = !mt.is_null() && *jl_symbol_name((*mt).name) == b'#' as _;
+                if jl_datatype_nfields(dt) > 0 || anonymous {
+                    FunctionKind::Closure
+                } else {
+                    FunctionKind::Generic
+                }
+            }
+        };
+        Ok(kind)
+    }
+
+    /// Call with a sequence of Value-s. The arguments are rooted for the
+    /// duration of the call, so temporaries can't be collected while Julia
+    /// runs.
     pub fn call<'a, I>(&self, args: I) -> Result<Value>
     where
         I: IntoIterator<Item = &'a Value>,
@@ -21,36 +176,191 @@ impl Function {
             argv.push(arg.lock()?);
         }
 
-        let ret = unsafe { jl_call(self.lock()?, argv.as_mut_ptr(), argv.len() as u32) };
-        jl_catch!();
+        let f = self.lock()?;
+        let ret = exception::isolated(|| {
+            let ret = unsafe { call_rooted(f, &argv) };
+            jl_catch!();
+            Ok(ret)
+        })?;
         Value::new(ret).map_err(|_| Error::CallError)
     }
 
+    /// Call with a slice of raw pointers, skipping the per-argument locking
+    /// done by `call`. Meant for hot loops which already hold the pointers.
+    ///
+    /// # Safety
+    /// Every pointer must be a valid Julia value which the caller keeps
+    /// rooted for the duration of the call.
+    pub unsafe fn call_raw(&self, args: &[*mut jl_value_t]) -> Result<Value> {
+        let f = self.lock()?;
+        let ret = exception::isolated(|| {
+            let ret = jl_call(f, args.as_ptr() as *mut _, args.len() as u32);
+            jl_catch!();
+            Ok(ret)
+        })?;
+        Value::new(ret).map_err(|_| Error::CallError)
+    }
+
+    /// Call with a tuple of Rust values, boxing each of them, e.g.
+    /// `f.call_with((1.0, 2))`.
+    pub fn call_with<A: IntoValues>(&self, args: A) -> Result<Value> {
+        self.call(&args.into_values())
+    }
+
+    /// Call with a slice of Value-s and convert the result to `T`.
+    pub fn call_into<T>(&self, args: &[&Value]) -> Result<T>
+    where
+        T: for<'a> TryFrom<&'a Value, Error = Error>,
+    {
+        let ret = self.call(args.iter().copied())?;
+        T::try_from(&ret)
+    }
+
+    /// Call with a slice of Value-s in the latest world age using
+    /// `Base.invokelatest`, so methods defined after the caller was compiled
+    /// are visible. This is slower than `call` since the method is looked
+    /// up dynamically on every call, use it only right after defining new
+    /// methods.
+    pub fn call_latest(&self, args: &[&Value]) -> Result<Value> {
+        let f = Value::new(self.lock()? as *mut jl_value_t)?;
+        let mut argv = vec![&f];
+        argv.extend_from_slice(args);
+        Function::base("invokelatest")?.call(argv)
+    }
+
+    /// Call with a slice of Value-s and split the returned tuple into its
+    /// elements, e.g. for functions with multiple return values.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the result isn't a tuple.
+    pub fn call_tuple(&self, args: &[&Value]) -> Result<Vec<Value>> {
+        let ret = self.call(args.iter().copied())?;
+        if !ret.is_tuple() {
+            return Err(Error::InvalidUnbox);
+        }
+        ret.tuple_fields()
+    }
+
+    /// Call with a slice of Value-s, measuring it like `@time`. Returns the
+    /// result, the elapsed wall time and the number of bytes allocated.
+    pub fn call_timed(&self, args: &[&Value]) -> Result<(Value, Duration, usize)> {
+        let f = Value::new(self.lock()? as *mut jl_value_t)?;
+        let mut argv = vec![&f];
+        argv.extend_from_slice(args);
+
+        let stats = timed_fn()?.call(argv)?;
+        let value = stats.get("value")?;
+        let time = f64::try_from(&stats.get("time")?)?;
+        let bytes = i64::try_from(&stats.get("bytes")?)?;
+        Ok((value, Duration::from_secs_f64(time), bytes as usize))
+    }
+
+    /// Call with a slice of Value-s on a new Task, interrupting it if it
+    /// takes longer than `dur`.
+    ///
+    /// The interrupt is only delivered when the callee reaches a yield point,
+    /// e.g. when it sleeps, waits or does IO. A call which never yields runs
+    /// to completion regardless of `dur`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::Timeout if the call was interrupted.
+    pub fn call_timeout(&self, args: &[&Value], dur: Duration) -> Result<Value> {
+        let f = Value::new(self.lock()? as *mut jl_value_t)?;
+        let secs = Value::from(dur.as_secs_f64());
+        let mut argv = vec![&f, &secs];
+        argv.extend_from_slice(args);
+
+        let ret = timeout_fn()?.call(argv)?;
+        let mut ret = ret.tuple_fields()?.into_iter();
+        let finished = ret.next().ok_or(Error::CallError)?;
+        let value = ret.next().ok_or(Error::CallError)?;
+        if bool::try_from(&finished)? {
+            Ok(value)
+        } else {
+            Err(Error::Timeout)
+        }
+    }
+
+    /// Call with a sequence of Value-s and keyword arguments.
+    pub fn call_kw<'a, I>(&self, args: I, kwargs: &[(&str, &Value)]) -> Result<Value>
+    where
+        I: IntoIterator<Item = &'a Value>,
+    {
+        let core = Module::new(unsafe { jl_core_module })?;
+        let tuple = core.function("tuple")?;
+
+        let mut names = vec![];
+        for (name, _) in kwargs {
+            names.push(Value::from_value(Symbol::with_name(*name)?)?);
+        }
+        let names = tuple.call(&names)?;
+        let values = tuple.call(kwargs.iter().map(|(_, value)| *value))?;
+
+        // NamedTuple{names}(values)
+        let namedtuple = Type::from_value(core.global("NamedTuple")?)?.apply_type1(&names)?;
+        let kws = Self::from_value(namedtuple)?.call1(&values)?;
+
+        let f = Value::new(self.lock()? as *mut jl_value_t)?;
+        let mut argv = vec![&kws, &f];
+        for arg in args {
+            argv.push(arg);
+        }
+        core.function("kwcall")?.call(argv)
+    }
+
+    /// Partially applies the function, returning a new function which calls
+    /// this one with `bound` prepended to its arguments.
+    pub fn partial(&self, bound: &[&Value]) -> Result<Self> {
+        let f = Value::new(self.lock()? as *mut jl_value_t)?;
+        let mut argv = vec![&f];
+        argv.extend_from_slice(bound);
+        let ret = partial_fn()?.call(argv)?;
+        Self::from_value(ret)
+    }
+
     /// Call with 0 Value-s.
     pub fn call0(&self) -> Result<Value> {
-        let ret = unsafe { jl_call0(self.lock()?) };
-        jl_catch!();
+        let f = self.lock()?;
+        let ret = exception::isolated(|| {
+            let ret = unsafe { jl_call0(f) };
+            jl_catch!();
+            Ok(ret)
+        })?;
         Value::new(ret).map_err(|_| Error::CallError)
     }
 
     /// Call with 1 Value.
     pub fn call1(&self, arg1: &Value) -> Result<Value> {
-        let ret = unsafe { jl_call1(self.lock()?, arg1.lock()?) };
-        jl_catch!();
+        let (f, arg1) = (self.lock()?, arg1.lock()?);
+        let ret = exception::isolated(|| {
+            let ret = unsafe { jl_call1(f, arg1) };
+            jl_catch!();
+            Ok(ret)
+        })?;
         Value::new(ret).map_err(|_| Error::CallError)
     }
 
     /// Call with 2 Value-s.
     pub fn call2(&self, arg1: &Value, arg2: &Value) -> Result<Value> {
-        let ret = unsafe { jl_call2(self.lock()?, arg1.lock()?, arg2.lock()?) };
-        jl_catch!();
+        let (f, arg1, arg2) = (self.lock()?, arg1.lock()?, arg2.lock()?);
+        let ret = exception::isolated(|| {
+            let ret = unsafe { jl_call2(f, arg1, arg2) };
+            jl_catch!();
+            Ok(ret)
+        })?;
         Value::new(ret).map_err(|_| Error::CallError)
     }
 
     /// Call with 3 Value-s.
     pub fn call3(&self, arg1: &Value, arg2: &Value, arg3: &Value) -> Result<Value> {
-        let ret = unsafe { jl_call3(self.lock()?, arg1.lock()?, arg2.lock()?, arg3.lock()?) };
-        jl_catch!();
+        let (f, arg1, arg2, arg3) = (self.lock()?, arg1.lock()?, arg2.lock()?, arg3.lock()?);
+        let ret = exception::isolated(|| {
+            let ret = unsafe { jl_call3(f, arg1, arg2, arg3) };
+            jl_catch!();
+            Ok(ret)
+        })?;
         Value::new(ret).map_err(|_| Error::CallError)
     }
 }
@@ -0,0 +1,107 @@
+//! Module providing an iterator over any Julia value implementing the
+//! iteration protocol.
+
+use super::{Array, Function, JlValue, Value};
+use crate::error::Result;
+use crate::sys::*;
+
+/// Global IdDict keeping Julia objects which are only referenced from Rust
+/// alive.
+fn roots() -> Result<Value> {
+    super::cached_eval("roots", "global __julia_rs_roots = IdDict{Any, Nothing}()")
+}
+
+/// Keeps `value` alive until it's passed to `unroot`.
+pub(crate) fn root(value: &Value) -> Result<()> {
+    Function::base("setindex!")?.call3(&roots()?, &Value::nothing(), value)?;
+    Ok(())
+}
+
+/// Allows `value`, previously passed to `root`, to be collected again.
+pub(crate) fn unroot(value: &Value) -> Result<()> {
+    Function::base("delete!")?.call2(&roots()?, value)?;
+    Ok(())
+}
+
+/// Iterator calling Julia's `iterate(x, state)` until it returns `nothing`.
+///
+/// The iterable and the current state are kept in a vector which is rooted
+/// for as long as the iterator is alive.
+pub struct JuliaIter {
+    iterate: Function,
+    // Any[iterable, (element, state)]
+    slots: Array,
+    started: bool,
+    done: bool,
+}
+
+impl JuliaIter {
+    pub(crate) fn new(iterable: &Value) -> Result<Self> {
+        let iterate = Function::base("iterate")?;
+        let slots = unsafe { jl_alloc_vec_any(2) };
+        jl_catch!();
+        let slots = Array::new(slots)?;
+        slots.index_set(0, iterable)?;
+
+        root(&Value::new(slots.lock()? as *mut jl_value_t)?)?;
+
+        Ok(Self {
+            iterate,
+            slots,
+            started: false,
+            done: false,
+        })
+    }
+
+    fn step(&mut self) -> Result<Option<Value>> {
+        let iterable = self.slots.index(0)?;
+        let next = if self.started {
+            let prev = self.slots.index(1)?;
+            let state = unsafe { jl_get_nth_field(prev.lock()?, 1) };
+            jl_catch!();
+            self.iterate.call2(&iterable, &Value::new(state)?)?
+        } else {
+            self.iterate.call1(&iterable)?
+        };
+        self.started = true;
+
+        if next.is_nothing() {
+            return Ok(None);
+        }
+        self.slots.index_set(1, &next)?;
+
+        let elem = unsafe { jl_get_nth_field(next.lock()?, 0) };
+        jl_catch!();
+        Value::new(elem).map(Some)
+    }
+}
+
+impl Iterator for JuliaIter {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.step() {
+            Ok(Some(elem)) => Some(Ok(elem)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl Drop for JuliaIter {
+    fn drop(&mut self) {
+        if let Ok(slots) = self.slots.lock() {
+            let _ = Value::new(slots as *mut jl_value_t).and_then(|slots| unroot(&slots));
+        }
+    }
+}
@@ -1,10 +1,19 @@
 //! Main entry point to the Julia api.
 
-use std::ffi::CString;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::ffi::{c_int, c_void, CString};
+use std::fs;
 use std::io::Read;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::{Arc, Mutex};
 
 use crate::error::{Error, Result};
-use crate::string::IntoCString;
+use crate::string::{julia_str_to_string, IntoCString};
 use crate::sys::*;
 use crate::version::Version;
 
@@ -16,6 +25,9 @@ macro_rules! jl_catch {
     () => {
         $crate::jl_catch!(|ex| { ex });
     };
+    (classify) => {
+        $crate::jl_catch!(|ex -> $crate::error::Error| $crate::error::Error::UnhandledException(ex).classify());
+    };
     (|$ex:ident| $body:expr) => {
         $crate::jl_catch!(|$ex -> $crate::error::Error::UnhandledException| $crate::error::Error::UnhandledException($body));
     };
@@ -29,29 +41,217 @@ macro_rules! jl_catch {
     }
 }
 
+/// Formats a string like `format!` and evaluates it with
+/// `Julia::eval_string`, e.g. `jl_eval!(jl, "x = {} + {}", a, b)`.
+///
+/// The arguments are pasted into the source as-is, they are not escaped.
+/// Formatting untrusted input lets it run arbitrary Julia code, pass such
+/// values as arguments to a `Function` instead.
+#[macro_export]
+macro_rules! jl_eval {
+    ($jl:expr, $($arg:tt)+) => {
+        $jl.eval_string(::std::format!($($arg)+))
+    };
+}
+
 pub mod array;
 pub mod datatype;
 pub mod exception;
 pub mod function;
+pub mod iter;
 pub mod module;
 pub mod primitive;
 pub mod sym;
 pub mod task;
 pub mod value;
 
-pub use self::array::{Array, Svec};
-pub use self::datatype::Datatype;
-pub use self::exception::Exception;
-pub use self::function::Function;
+pub use self::array::{Array, ArrayAccess, ArrayElement, ArrayIntoIter, ByteArray, Svec};
+pub use self::datatype::{Datatype, Type, UnionAll};
+pub use self::exception::{BoundsInfo, Exception, MethodInfo, TypeErrorInfo};
+pub use self::function::{Function, FunctionKind, IntoValues};
+pub use self::iter::JuliaIter;
 pub use self::module::Module;
 pub use self::primitive::*;
 pub use self::sym::{IntoSymbol, Symbol};
 pub use self::task::Task;
-pub use self::value::{JlValue, Value};
+pub use self::value::{FromJulia, JlValue, RustValue, Value, ValueById};
+
+thread_local! {
+    /// Values evaluated by `cached_eval`, by key.
+    static CACHED: RefCell<HashMap<&'static str, Value>> = RefCell::new(HashMap::new());
+}
+
+/// Evaluates `src` the first time it's called with `key` and returns the
+/// same value on every later call, e.g. for helper closures.
+pub(crate) fn cached_eval(key: &'static str, src: &str) -> Result<Value> {
+    CACHED.with(|cached| {
+        if let Some(value) = cached.borrow().get(key) {
+            return Ok(value.clone());
+        }
+
+        let src = src.into_cstring();
+        let raw = unsafe { jl_eval_string(src.as_ptr()) };
+        jl_catch!();
+        let value = Value::new(raw)?;
+        cached.borrow_mut().insert(key, value.clone());
+        Ok(value)
+    })
+}
+
+/// Like `cached_eval`, for sources evaluating to a function.
+pub(crate) fn cached_function(key: &'static str, src: &str) -> Result<Function> {
+    Function::new(cached_eval(key, src)?.lock()?)
+}
+
+type Finalizer = Box<dyn FnOnce() + Send>;
+
+/// Called from Julia with a pointer obtained from `Box::into_raw`, which is
+/// consumed so the closure can't be called twice.
+extern "C" fn run_finalizer(data: *mut c_void) {
+    let f = unsafe { Box::from_raw(data as *mut Finalizer) };
+    // unwinding into Julia isn't possible
+    let _ = panic::catch_unwind(AssertUnwindSafe(f));
+}
+
+/// Julia function which returns a finalizer calling `fptr(data)`.
+fn finalizer_fn() -> Result<Function> {
+    cached_function(
+        "finalizer",
+        "(fptr, data) -> _ -> ccall(fptr, Cvoid, (Ptr{Cvoid},), data)",
+    )
+}
+
+/// Julia function which evaluates a string in Main while capturing stderr,
+/// log messages of level Warn and above and optionally stdout. Returns
+/// `(value, stdout, stderr, messages)`. The logger type lives in an
+/// anonymous module.
+fn capture_fn() -> Result<Function> {
+    cached_function(
+        "capture",
+        r#"Core.eval(Module(), Expr(:toplevel, quote
+            using Logging: AbstractLogger, Warn, with_logger
+            import Logging: handle_message, shouldlog, min_enabled_level, catch_exceptions
+
+            struct Collector <: AbstractLogger
+                messages::Vector{String}
+            end
+            min_enabled_level(::Collector) = Warn
+            shouldlog(::Collector, args...) = true
+            catch_exceptions(::Collector) = true
+            handle_message(c::Collector, level, msg, args...; kwargs...) =
+                push!(c.messages, string(level, ": ", msg))
+
+            # redirect restores the stream when f returns or throws
+            function capture(f, redirect)
+                mktemp() do _, io
+                    value = redirect(f, io)
+                    flush(io)
+                    seekstart(io)
+                    (value, read(io, String))
+                end
+            end
+
+            function (src, with_stdout)
+                logger = Collector(String[])
+                run() = with_logger(logger) do
+                    Core.eval(Main, Meta.parseall(src))
+                end
+                redirect = with_stdout ? redirect_stdout : (f, _) -> f()
+                ((value, err), out) = capture(redirect) do
+                    capture(run, redirect_stderr)
+                end
+                (value, out, err, logger.messages)
+            end
+        end.args...))"#,
+    )
+}
+
+/// The result of `Julia::eval_with_diagnostics`.
+#[derive(Debug)]
+pub struct Diagnostics {
+    /// The value of the expression.
+    pub value: Value,
+    /// Everything printed to stderr, except for log messages.
+    pub stderr: String,
+    /// Log messages of level Warn and above, as `Level: message`.
+    pub warnings: Vec<String>,
+}
+
+/// Everything evaluating a cell produced, returned by `Julia::exec_cell`.
+#[derive(Debug)]
+pub struct CellOutput {
+    /// The value of the last statement.
+    pub value: Value,
+    /// Everything printed to stdout.
+    pub stdout: String,
+    /// Everything printed to stderr, except for log messages.
+    pub stderr: String,
+    /// Log messages of level Warn and above, as `Level: message`.
+    pub warnings: Vec<String>,
+}
+
+/// Loads the package or standard library `name` into Main, like `import`,
+/// failing if it isn't available.
+pub(crate) fn require(name: &str) -> Result<Module> {
+    let main = Value::new(unsafe { jl_main_module } as *mut jl_value_t)?;
+    let name = Value::from_value(Symbol::with_name(name)?)?;
+    let module = Function::base("require")?.call2(&main, &name)?;
+    Module::from_value(module)
+}
+
+/// Sources up to this length are included in parse errors.
+const MAX_SNIPPET_LEN: usize = 256;
+
+/// Maps a syntax error thrown while evaluating `src` to Error::ParseError,
+/// any other exception is returned as Error::UnhandledException.
+fn parse_error(ex: Exception, src: &str) -> Error {
+    let msg = match ex.message() {
+        Ok(msg) => msg,
+        Err(_) => return Error::UnhandledException(ex),
+    };
+    // the flisp parser reports syntax errors as ErrorExceptions
+    let is_syntax = match ex {
+        Exception::Parse(_) => true,
+        Exception::Error(_) => msg.starts_with("syntax:"),
+        _ => false,
+    };
+    if !is_syntax {
+        return Error::UnhandledException(ex);
+    }
+
+    if src.len() <= MAX_SNIPPET_LEN {
+        Error::ParseError(format!("{}\nin source:\n{}", msg, src))
+    } else {
+        Error::ParseError(msg)
+    }
+}
 
 /// Blank struct for controlling the Julia garbage collector.
 pub struct Gc;
 
+/// Kind of collection issued by `Gc::collect_mode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GcMode {
+    /// Let the collector decide between a full and an incremental
+    /// collection.
+    Auto,
+    /// Collect every generation.
+    Full,
+    /// Only collect the young generation, which is cheaper and pauses for
+    /// less time.
+    Incremental,
+}
+
+impl GcMode {
+    fn as_raw(self) -> jl_gc_collection_t {
+        match self {
+            GcMode::Auto => jl_gc_collection_t_JL_GC_AUTO,
+            GcMode::Full => jl_gc_collection_t_JL_GC_FULL,
+            GcMode::Incremental => jl_gc_collection_t_JL_GC_INCREMENTAL,
+        }
+    }
+}
+
 impl Gc {
     /// Enable or disable the garbage collector.
     pub fn enable(&mut self, p: bool) -> Result<()> {
@@ -62,11 +262,55 @@ impl Gc {
         Ok(())
     }
 
+    /// Add a Rust closure as finalizer, which is run once when the object is
+    /// collected or when Julia exits. Only mutable objects can be finalized.
+    /// The closure runs on whichever thread collects the object, so it has
+    /// to be Send.
+    pub fn add_finalizer<T, V, F>(&mut self, value: &V, f: F) -> Result<()>
+    where
+        V: JlValue<T>,
+        F: FnOnce() + Send + 'static,
+    {
+        let value = Value::new(value.lock()? as *mut jl_value_t)?;
+        let data = Box::into_raw(Box::new(Box::new(f) as Finalizer));
+
+        let register = || -> Result<()> {
+            let fptr = unsafe { jl_box_voidpointer(run_finalizer as *mut c_void) };
+            let fptr = Value::new(fptr)?;
+            let ptr = Value::new(unsafe { jl_box_voidpointer(data as *mut c_void) })?;
+            let finalizer = finalizer_fn()?.call2(&fptr, &ptr)?;
+            Function::base("finalizer")?.call2(&finalizer, &value)?;
+            Ok(())
+        };
+
+        // if registering failed, the closure is still ours to drop
+        register().inspect_err(|_| drop(unsafe { Box::from_raw(data) }))
+    }
+
     /// Check to see if gc is enabled.
     pub fn is_enabled(&self) -> bool {
         unsafe { jl_gc_is_enabled() != 0 }
     }
 
+    /// Enable or disable printing of timing information to stderr every time
+    /// a garbage collection occurs.
+    pub fn enable_logging(&mut self, on: bool) {
+        unsafe {
+            jl_enable_gc_logging(on as i32);
+        }
+    }
+
+    /// Gives the garbage collector a chance to run. Calls into Julia are
+    /// safepoints already, but a thread running a long Rust loop without
+    /// them blocks every collection. With multiple Julia threads, the other
+    /// threads wait until this one reaches a safepoint, so such loops should
+    /// call this regularly.
+    pub fn safepoint(&self) {
+        unsafe {
+            jl_gc_safepoint();
+        }
+    }
+
     /// Collect immediately. Set full to true if a full garbage collection
     /// should be issued
     pub fn collect(&mut self, full: bool) -> Result<()> {
@@ -76,16 +320,224 @@ impl Gc {
         jl_catch!();
         Ok(())
     }
+
+    /// Collect immediately, issuing the given kind of collection. Use
+    /// GcMode::Incremental to keep pauses short in latency-sensitive
+    /// embeddings.
+    pub fn collect_mode(&mut self, mode: GcMode) -> Result<()> {
+        unsafe {
+            jl_gc_collect(mode.as_raw());
+        }
+        jl_catch!();
+        Ok(())
+    }
+}
+
+/// Julia function which creates an AsyncCondition throwing an
+/// InterruptException in the calling task whenever it's notified. Returns
+/// `(condition, uv_async_t pointer, uv_async_send pointer)`.
+fn interrupter_fn() -> Result<Function> {
+    cached_function(
+        "interrupter",
+        r#"() -> begin
+            task = current_task()
+            cond = Base.AsyncCondition() do _
+                try
+                    istaskdone(task) || task === current_task() ||
+                        schedule(task, InterruptException(); error=true)
+                catch
+                end
+            end
+            (cond, cond.handle, cglobal(:uv_async_send))
+        end"#,
+    )
+}
+
+type UvAsyncSend = unsafe extern "C" fn(*mut c_void) -> c_int;
+
+/// The AsyncCondition set up by `interrupter_fn`, shared by a Julia handle
+/// and all its InterruptHandles.
+#[derive(Debug)]
+struct InterruptState {
+    // cleared when the at_exit hooks run
+    alive: bool,
+    // the uv_async_t and uv_async_send, as addresses so the state is Send
+    notify: Option<(usize, usize)>,
+}
+
+/// Handle for interrupting running Julia code, which unlike Julia can be
+/// sent to another thread. Created by `Julia::interrupt_handle`.
+///
+/// The handle stops working once the runtime it was created from is shut
+/// down.
+#[derive(Clone, Debug)]
+pub struct InterruptHandle {
+    state: Arc<Mutex<InterruptState>>,
+}
+
+impl InterruptHandle {
+    /// Throws an InterruptException in the task the handle interrupts. See
+    /// `Julia::interrupt`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the runtime was shut down or
+    /// Julia's event loop couldn't be notified.
+    pub fn interrupt(&self) -> Result<()> {
+        // held while notifying, so the runtime can't shut down in between
+        let state = self.state.lock().map_err(|_| Error::PoisonError)?;
+        let (handle, send) = match (state.alive, state.notify) {
+            (true, Some(notify)) => notify,
+            _ => {
+                return Err(Error::InvalidArgument(
+                    "the Julia runtime was shut down".to_owned(),
+                ))
+            }
+        };
+        // uv_async_send is the one libuv function safe to call from any thread
+        let send = unsafe { mem::transmute::<usize, UvAsyncSend>(send) };
+        let ret = unsafe { send(handle as *mut c_void) };
+        if ret != 0 {
+            return Err(Error::InvalidArgument(format!(
+                "couldn't notify Julia's event loop: error {}",
+                ret
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Restores the active project it was created with when it's dropped.
+struct ProjectGuard {
+    previous: Option<Value>,
+}
+
+impl ProjectGuard {
+    /// Saves the currently active project, as set in `Base.ACTIVE_PROJECT`.
+    fn save() -> Result<Self> {
+        let base = Module::new(unsafe { jl_base_module })?;
+        let active = base.global("ACTIVE_PROJECT")?;
+        let previous = base.function("getindex")?.call1(&active)?;
+        Ok(Self {
+            previous: Some(previous),
+        })
+    }
+
+    /// Restores the saved project, returning any error that occurs.
+    fn restore(mut self) -> Result<()> {
+        match self.previous.take() {
+            Some(previous) => Function::base("set_active_project")?
+                .call1(&previous)
+                .map(|_| ()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ProjectGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            let _ = Function::base("set_active_project").and_then(|f| f.call1(&previous));
+        }
+    }
+}
+
+/// Returns the version of the Julia runtime, or None if it isn't initialized.
+pub fn julia_version() -> Option<Version<'static>> {
+    if !Julia::is_initialized() {
+        return None;
+    }
+
+    unsafe {
+        let major = jl_ver_major() as u32;
+        let minor = jl_ver_minor() as u32;
+        let patch = jl_ver_patch() as u32;
+        let release = jl_ver_is_release() != 0;
+
+        Some(Version {
+            name: "julia",
+            major,
+            minor,
+            patch,
+            release,
+        })
+    }
+}
+
+/// Options for initializing the Julia runtime with `Julia::with_options`.
+#[derive(Clone, Debug, Default)]
+pub struct JuliaOptions {
+    quiet: bool,
+    image_path: Option<String>,
+    handle_signals: Option<bool>,
+}
+
+impl JuliaOptions {
+    /// Construct the default options, which are the same `Julia::new` uses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether Julia should stay quiet, disabling the banner,
+    /// deprecation warnings and method overwrite warnings.
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Sets the sysimage to load instead of the default one.
+    pub fn image(mut self, image_path: &str) -> Self {
+        self.image_path = Some(image_path.to_owned());
+        self
+    }
+
+    /// Sets whether Julia installs its signal handlers, like
+    /// `--handle-signals`. Without them, Ctrl-C is left to the host and
+    /// never becomes an InterruptException, see
+    /// `Julia::install_sigint_handler`.
+    pub fn handle_signals(mut self, handle: bool) -> Self {
+        self.handle_signals = Some(handle);
+        self
+    }
+
+    /// Writes the options to `jl_options`, which Julia reads while it's
+    /// initialized.
+    fn apply(&self) {
+        if let Some(handle) = self.handle_signals {
+            let handle = if handle {
+                JL_OPTIONS_HANDLE_SIGNALS_ON
+            } else {
+                JL_OPTIONS_HANDLE_SIGNALS_OFF
+            };
+            unsafe { jl_options.handle_signals = handle as i8 };
+        }
+        if self.quiet {
+            unsafe {
+                jl_options.quiet = 1;
+                jl_options.banner = 0;
+                jl_options.depwarn = JL_OPTIONS_DEPWARN_OFF as i8;
+                jl_options.warn_overwrite = JL_OPTIONS_WARN_OVERWRITE_OFF as i8;
+            }
+        }
+    }
 }
 
 /// Struct for controlling the Julia runtime.
+///
+/// A handle created with `Julia::new` or `Julia::new_with_image` owns the
+/// runtime and runs the at_exit hooks, shutting Julia down, when it's
+/// dropped. A handle created with `Julia::new_unchecked` borrows a runtime
+/// initialized elsewhere, e.g. by a host calling into Rust, and leaves it
+/// running. Use `Julia::leak` to give up ownership of an owning handle.
 pub struct Julia {
+    version: Version<'static>,
     main: Module,
     core: Module,
     base: Module,
     top: Module,
     at_exit: Option<i32>,
     gc: Gc,
+    interrupt: Arc<Mutex<InterruptState>>,
 }
 
 impl Julia {
@@ -99,22 +551,25 @@ impl Julia {
     ///
     /// Panics if the Julia runtime was not previously initialized.
     pub unsafe fn new_unchecked() -> Self {
-        if !Self::is_initialized() {
-            panic!("Julia is not initialized");
-        }
-
+        // the version is only available once Julia is initialized
+        let version = julia_version().expect("Julia is not initialized");
         let main = Module::new_unchecked(jl_main_module);
         let core = Module::new_unchecked(jl_core_module);
         let base = Module::new_unchecked(jl_base_module);
         let top = Module::new_unchecked(jl_top_module);
 
         Self {
+            version,
             main,
             core,
             base,
             top,
             at_exit: None,
             gc: Gc,
+            interrupt: Arc::new(Mutex::new(InterruptState {
+                alive: true,
+                notify: None,
+            })),
         }
     }
 
@@ -138,6 +593,31 @@ impl Julia {
         Ok(jl)
     }
 
+    /// Initialize the Julia runtime with `options`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::JuliaInitialized if Julia is already initialized.
+    pub fn with_options(options: &JuliaOptions) -> Result<Self> {
+        if Self::is_initialized() {
+            return Err(Error::JuliaInitialized);
+        }
+
+        options.apply();
+        match options.image_path {
+            Some(ref image_path) => {
+                let image_path = image_path.as_str().into_cstring();
+                unsafe { jl_init_with_image(ptr::null(), image_path.as_ptr()) };
+            }
+            None => unsafe { jl_init() },
+        }
+        jl_catch!();
+
+        let mut jl = unsafe { Self::new_unchecked() };
+        jl.at_exit = Some(0);
+        Ok(jl)
+    }
+
     /// Initialize the Julia runtime with a specific sysimage.
     ///
     /// ## Errors
@@ -161,21 +641,138 @@ impl Julia {
     }
 
     /// Returns the version of currently running Julia runtime.
-    pub fn version(&self) -> Version {
-        unsafe {
-            let major = jl_ver_major() as u32;
-            let minor = jl_ver_minor() as u32;
-            let patch = jl_ver_patch() as u32;
-            let release = jl_ver_is_release() != 0;
-
-            Version {
-                name: "julia",
-                major,
-                minor,
-                patch,
-                release,
+    pub const fn version(&self) -> &Version<'static> {
+        &self.version
+    }
+
+    /// Hands Ctrl-C to Julia, or back to the host if `install` is false.
+    ///
+    /// With `install` true, Julia's SIGINT handler is installed and SIGINT
+    /// throws an InterruptException in the code running on Julia's main
+    /// thread once it reaches a safepoint, which the code may catch. This
+    /// needs Julia's signal handling, which is on by default.
+    ///
+    /// With `install` false, SIGINT is restored to the default action and
+    /// unblocked on the calling thread, so the host receives it, e.g.
+    /// through a handler it installs afterwards. Julia code never sees an
+    /// InterruptException caused by Ctrl-C in this mode. On Unix, Julia's
+    /// signal listener thread competes for SIGINT sent to the process as
+    /// long as Julia handles signals, so hosts which own Ctrl-C should also
+    /// start Julia with `JuliaOptions::handle_signals(false)`.
+    pub fn install_sigint_handler(&mut self, install: bool) {
+        if install {
+            unsafe {
+                jl_install_sigint_handler();
+                jl_exit_on_sigint(0);
             }
+            return;
+        }
+
+        unsafe {
+            libc::signal(libc::SIGINT, libc::SIG_DFL);
+        }
+        #[cfg(unix)]
+        unsafe {
+            let mut set = std::mem::zeroed();
+            libc::sigemptyset(&mut set);
+            libc::sigaddset(&mut set, libc::SIGINT);
+            libc::pthread_sigmask(libc::SIG_UNBLOCK, &set, ptr::null_mut());
+        }
+    }
+
+    /// Throws an InterruptException in the task running Julia code on this
+    /// thread, cancelling it unless it catches the exception.
+    ///
+    /// The exception is scheduled through Julia's event loop rather than a
+    /// signal, so it works the same whatever was set with
+    /// `Julia::install_sigint_handler` and never reaches the host. It only
+    /// takes effect once the running code reaches a yield point, e.g. when
+    /// it sleeps, waits for IO or calls `yield`. Loops which never yield
+    /// can't be interrupted. If no Julia code is running, the exception is
+    /// thrown at the next yield point instead. Use `Julia::interrupt_handle`
+    /// to interrupt from another thread.
+    pub fn interrupt(&self) -> Result<()> {
+        self.interrupt_handle()?.interrupt()
+    }
+
+    /// Returns a handle which can interrupt Julia from another thread, e.g.
+    /// to cancel a long computation when a request is aborted. The handle
+    /// interrupts the task which first created a handle, normally the one
+    /// running code evaluated from Rust.
+    pub fn interrupt_handle(&self) -> Result<InterruptHandle> {
+        let mut state = self.interrupt.lock().map_err(|_| Error::PoisonError)?;
+        if state.notify.is_none() {
+            let ret = interrupter_fn()?.call0()?.tuple_fields()?;
+            let (cond, handle, send) = match ret.as_slice() {
+                [cond, handle, send] => (cond, handle, send),
+                _ => return Err(Error::InvalidUnbox),
+            };
+            // the condition has to outlive every handle
+            iter::root(cond)?;
+            let handle = unsafe { jl_unbox_voidpointer(handle.lock()?) };
+            let send = unsafe { jl_unbox_voidpointer(send.lock()?) };
+            state.notify = Some((handle as usize, send as usize));
+        }
+        Ok(InterruptHandle {
+            state: self.interrupt.clone(),
+        })
+    }
+
+    /// Marks the runtime as shut down and runs the at_exit hooks.
+    fn run_at_exit(&self, status: i32) {
+        if let Ok(mut state) = self.interrupt.lock() {
+            state.alive = false;
+        }
+        unsafe { jl_atexit_hook(status) }
+    }
+
+    /// Returns the banner Julia prints on startup, as produced by
+    /// `Base.banner`.
+    pub fn banner(&self) -> Result<String> {
+        let banner = self.base.global("banner")?;
+        let banner = self.base.function("sprint")?.call1(&banner)?;
+        String::try_from(&banner)
+    }
+
+    /// Returns the number of logical CPU cores, `Sys.CPU_THREADS`.
+    pub fn cpu_threads(&self) -> Result<usize> {
+        let sys = Module::from_value(self.base.global("Sys")?)?;
+        let threads = sys.global("CPU_THREADS")?;
+        i64::try_from(&threads).map(|n| n as usize)
+    }
+
+    /// Returns the target triple of the machine Julia was built for,
+    /// `Sys.MACHINE`.
+    pub fn machine(&self) -> Result<String> {
+        let sys = Module::from_value(self.base.global("Sys")?)?;
+        String::try_from(&sys.global("MACHINE")?)
+    }
+
+    /// Returns the CPU target code is generated for, as passed with
+    /// `--cpu-target`, e.g. `native` or a multi-versioned list such as
+    /// `generic;sandybridge,-xsaveopt,clone_all`. Defaults to `native`.
+    pub fn cpu_target(&self) -> Result<String> {
+        let target = unsafe { jl_options.cpu_target };
+        if target.is_null() {
+            return Ok("native".to_owned());
         }
+        unsafe { julia_str_to_string(target) }
+    }
+
+    /// Checks if code is generated for a generic CPU rather than for the
+    /// host, i.e. if the first entry of `cpu_target` is `generic`. Such
+    /// code runs on any CPU of the architecture, but may be slower.
+    pub fn is_generic_target(&self) -> Result<bool> {
+        let target = self.cpu_target()?;
+        let name = target.split([';', ',']).next().unwrap_or_default();
+        Ok(name == "generic")
+    }
+
+    /// Returns the name of the host CPU as detected by LLVM,
+    /// `Sys.CPU_NAME`, e.g. `skylake`.
+    pub fn cpu_name(&self) -> Result<String> {
+        let sys = Module::from_value(self.base.global("Sys")?)?;
+        String::try_from(&sys.global("CPU_NAME")?)
     }
 
     /// Returns a reference to the garbage collector.
@@ -204,6 +801,38 @@ impl Julia {
         self.at_exit = at_exit;
     }
 
+    /// Consumes the handle without running the at_exit hooks, leaving the
+    /// runtime running. Use this when Julia has to outlive the handle, a new
+    /// handle can be obtained with `Julia::new_unchecked`.
+    pub fn leak(mut self) {
+        self.at_exit = None;
+    }
+
+    /// Runs pending finalizers, flushes Julia's stdout and stderr and then
+    /// runs the at_exit hooks, consuming Julia.
+    ///
+    /// Unlike dropping the handle, any error that occurs on the way is
+    /// returned. If an error is returned, the at_exit hooks still run when
+    /// the handle is dropped. A handle which doesn't own the runtime (see
+    /// `Julia::new_unchecked`) only runs the finalizers and flushes.
+    pub fn shutdown(mut self) -> Result<()> {
+        self.gc.collect(true)?;
+        unsafe {
+            jl_gc_run_pending_finalizers(ptr::null_mut());
+        }
+        jl_catch!();
+
+        let flush = self.base.function("flush")?;
+        for stream in ["stdout", "stderr"] {
+            flush.call1(&self.base.global(stream)?)?;
+        }
+
+        if let Some(s) = self.at_exit.take() {
+            self.run_at_exit(s);
+        }
+        Ok(())
+    }
+
     /// Returns a handle to the main module.
     pub const fn main(&self) -> &Module {
         &self.main
@@ -224,8 +853,83 @@ impl Julia {
         &self.top
     }
 
-    /// Loads a Julia script from any Read without evaluating it.
-    pub fn load<R: Read, S: IntoCString>(&mut self, r: &mut R, name: Option<S>) -> Result<Value> {
+    /// Returns the function at a dotted path like `Base.Math.sqrt`,
+    /// resolving every prefix as a module. The path is resolved from the
+    /// main module, so a package has to be imported before its functions
+    /// can be looked up, while names exported by Base work on their own.
+    ///
+    /// ## Errors
+    /// Returns `Error::UndefVar` with the path up to the missing name if a
+    /// name isn't defined, `Error::InvalidArgument` if a prefix isn't a
+    /// module and `Error::NotAFunction` if the last name isn't a function.
+    pub fn function(&self, path: &str) -> Result<Function> {
+        let mut module = self.main.clone();
+        let mut end = 0;
+        let mut names = path.split('.').peekable();
+        while let Some(name) = names.next() {
+            end += name.len();
+            let value = match module.global(name) {
+                Ok(value) => value,
+                Err(Error::NullPointer) => return Err(Error::UndefVar(path[..end].to_owned())),
+                Err(err) => return Err(err),
+            };
+
+            if names.peek().is_none() {
+                let raw = value.lock()?;
+                if unsafe { jl_isa(raw, jl_function_type as *mut _) } == 0 {
+                    return Err(Error::NotAFunction);
+                }
+                return Function::new(raw);
+            }
+            if !value.is_module() {
+                let msg = format!("{} is not a module", &path[..end]);
+                return Err(Error::InvalidArgument(msg));
+            }
+            module = Module::new(value.lock()? as *mut jl_module_t)?;
+            end += 1;
+        }
+        unreachable!("split yields at least one name")
+    }
+
+    /// Parses a Julia script from any Read without evaluating it. Returns a
+    /// `:toplevel` Expr holding every top-level statement. `name` is the
+    /// file name used in line numbers and errors.
+    ///
+    /// Syntax errors aren't thrown, they're returned as `:error` or
+    /// `:incomplete` expressions in place of the offending statement.
+    pub fn parse_file<R: Read, S: IntoCString>(
+        &mut self,
+        r: &mut R,
+        name: Option<S>,
+    ) -> Result<Value> {
+        let mut content = String::new();
+        r.read_to_string(&mut content)?;
+        let name = name
+            .map(|s| s.into_cstring())
+            .unwrap_or_else(|| "string".into_cstring());
+        let name = name.as_bytes();
+
+        let raw = unsafe {
+            jl_parse_all(
+                content.as_ptr() as *const _,
+                content.len(),
+                name.as_ptr() as *const _,
+                name.len(),
+                1,
+            )
+        };
+        jl_catch!();
+        Value::new(raw)
+    }
+
+    /// Loads a Julia script from any Read and evaluates its top-level
+    /// statements in Main, like `include`. Returns the value of the last
+    /// statement. `name` is the file name used in line numbers and errors.
+    pub fn load_file<R: Read, S: IntoCString>(
+        &mut self,
+        r: &mut R,
+        name: Option<S>,
+    ) -> Result<Value> {
         let mut content = String::new();
         let len = r.read_to_string(&mut content)?;
         let content = content.into_cstring();
@@ -236,27 +940,245 @@ impl Julia {
             .unwrap_or_else(|| "string".into_cstring());
         let name = name.as_ptr();
 
-        //let raw = unsafe { jl_load_file_string(content, len, ptr::null::<i8>() as *mut _) };
         let raw = unsafe { jl_load_file_string(content, len, name as *mut _, jl_main_module) };
         jl_catch!();
         Value::new(raw)
     }
 
     /// Parses and evaluates string.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::ParseError if the string isn't valid Julia syntax.
     pub fn eval_string<S: IntoCString>(&mut self, string: S) -> Result<Value> {
-        let string = string.into_cstring();
-        let string = string.as_ptr();
+        let source = string.into_cstring();
+        let string = source.as_ptr();
 
-        let ret = unsafe { jl_eval_string(string) };
-        jl_catch!();
+        let ret = exception::isolated(|| {
+            let ret = unsafe { jl_eval_string(string) };
+            jl_catch!(|ex -> Error| parse_error(ex, &source.to_string_lossy()));
+            Ok(ret)
+        })?;
         Value::new(ret).map_err(|_| Error::EvalError)
     }
+
+    /// Parses and evaluates string like `eval_string`, returning the
+    /// warnings emitted along the way, e.g. by `@warn` or deprecations,
+    /// instead of letting them go to stderr, see `Diagnostics`. Only log
+    /// records of level Warn and above count as warnings, anything else
+    /// printed to stderr is returned separately.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::ParseError if the string isn't valid Julia syntax. The
+    /// warnings are lost if evaluation fails.
+    pub fn eval_with_diagnostics(&mut self, expr: &str) -> Result<Diagnostics> {
+        let cell = self.capture(expr, false)?;
+        Ok(Diagnostics {
+            value: cell.value,
+            stderr: cell.stderr,
+            warnings: cell.warnings,
+        })
+    }
+
+    /// Parses and evaluates every statement of `src` in Main like a notebook
+    /// cell, capturing what it prints to stdout and stderr and the warnings
+    /// it logs, see `CellOutput`. The streams are restored afterwards, even
+    /// if evaluation fails.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::ParseError if the string isn't valid Julia syntax.
+    /// The captured output is lost if evaluation fails.
+    pub fn exec_cell(&mut self, src: &str) -> Result<CellOutput> {
+        self.capture(src, true)
+    }
+
+    /// Evaluates `src` with `capture_fn`, leaving stdout alone unless
+    /// `with_stdout` is set.
+    fn capture(&mut self, src: &str, with_stdout: bool) -> Result<CellOutput> {
+        let ret = capture_fn()?
+            .call_tuple(&[&Value::from(src), &Value::from(with_stdout)])
+            .map_err(|err| match err {
+                Error::UnhandledException(ex) => parse_error(ex, src),
+                err => err,
+            })?;
+        let mut ret = ret.into_iter();
+        let (value, stdout, stderr, warnings) =
+            match (ret.next(), ret.next(), ret.next(), ret.next()) {
+                (Some(value), Some(stdout), Some(stderr), Some(warnings)) => {
+                    (value, stdout, stderr, warnings)
+                }
+                _ => return Err(Error::InvalidUnbox),
+            };
+        let warnings = Array::from_value(warnings)?
+            .as_vec()?
+            .iter()
+            .map(String::try_from)
+            .collect::<Result<_>>()?;
+        Ok(CellOutput {
+            value,
+            stdout: String::try_from(&stdout)?,
+            stderr: String::try_from(&stderr)?,
+            warnings,
+        })
+    }
+
+    /// Reconstructs a value serialized with `Value::serialize`, using the
+    /// `Serialization` standard library.
+    ///
+    /// The types of the serialized value have to be defined, with the same
+    /// layout, in this process. Serialized data is only guaranteed to be
+    /// readable by the same Julia version.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UnhandledException if the data is invalid or refers to
+    /// types or modules which aren't loaded.
+    pub fn deserialize(&mut self, bytes: &[u8]) -> Result<Value> {
+        let serialization = require("Serialization")?;
+        let bytes = Value::from_value(ByteArray::from_slice(bytes)?)?;
+        let buf = self.base.function("IOBuffer")?.call1(&bytes)?;
+        serialization.function("deserialize")?.call1(&buf)
+    }
+
+    /// Parses every top-level statement of `src` and evaluates them in
+    /// sequence in Main, returning the value of the last one, like
+    /// `include` does for a file.
+    ///
+    /// ## Errors
+    ///
+    /// Evaluation stops at the first statement that throws, its exception is
+    /// returned. Syntax errors are thrown when their statement is reached.
+    pub fn eval_script(&mut self, src: &str) -> Result<Value> {
+        let filename = "none";
+        exception::isolated(|| {
+            let ex = unsafe {
+                jl_parse_all(
+                    src.as_ptr() as *const _,
+                    src.len(),
+                    filename.as_ptr() as *const _,
+                    filename.len(),
+                    1,
+                )
+            };
+            jl_catch!();
+            let ex = Value::new(ex)?;
+            let main = Value::new(self.main.lock()? as *mut jl_value_t)?;
+            // a :toplevel expression evaluates its statements one by one
+            self.core.function("eval")?.call2(&main, &ex)
+        })
+    }
+
+    /// Parses and evaluates string for its side effects, discarding the
+    /// result.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::ParseError if the string isn't valid Julia syntax.
+    pub fn run<S: IntoCString>(&mut self, stmt: S) -> Result<()> {
+        self.eval_string(stmt).map(|_| ())
+    }
+
+    /// Parses and evaluates string in `module`, resolving bindings in that
+    /// module rather than in Main.
+    ///
+    /// Evaluates through `Core.eval`, which calls `jl_toplevel_eval_in`
+    /// inside `jl_call`, so both parse errors and exceptions are returned
+    /// as errors.
+    pub fn eval_in(&mut self, module: &Module, expr: &str) -> Result<Value> {
+        exception::isolated(|| {
+            let meta = Module::from_value(self.base.global("Meta")?)?;
+            let expr = meta.function("parseall")?.call1(&Value::from(expr))?;
+            let module = Value::new(module.lock()? as *mut jl_value_t)?;
+            self.core.function("eval")?.call2(&module, &expr)
+        })
+    }
+
+    /// Creates a new module called `name`, with Main as its parent but not
+    /// bound in it. Code evaluated with `eval_in` runs isolated from Main.
+    /// The module always uses Core, set `import_base` to also use Base.
+    pub fn new_module(&mut self, name: &str, import_base: bool) -> Result<Module> {
+        let name = Symbol::with_name(name)?;
+        let raw = unsafe { jl_new_module(name.lock()?, self.main.lock()?) };
+        jl_catch!();
+        if import_base {
+            unsafe { jl_module_using(raw, self.base.lock()?) };
+            jl_catch!();
+        }
+        Module::new(raw)
+    }
+
+    /// Wraps the expression in a Task and schedules it, returning without
+    /// waiting for it to finish.
+    ///
+    /// The Task only makes progress when the running task yields, i.e. when
+    /// `Task::wait` or `Task::fetch` is called or when other Julia code
+    /// sleeps, waits or does IO. Long computations without yield points
+    /// run to completion once started.
+    pub fn eval_async(&mut self, expr: &str) -> Result<Task> {
+        let task = self.eval_string(format!("@async begin\n{}\nend", expr))?;
+        Task::from_value(task)
+    }
+
+    /// Returns the path to the Project.toml of the active project, if any.
+    pub fn active_project(&self) -> Result<Option<PathBuf>> {
+        let project = self.base.function("active_project")?.call0()?;
+        if project.is_nothing() {
+            return Ok(None);
+        }
+        String::try_from(&project).map(|p| Some(PathBuf::from(p)))
+    }
+
+    /// Activates the project at `path`, which can be either a directory or a
+    /// Project.toml. Non UTF-8 paths are converted lossily.
+    pub fn activate_project(&mut self, path: &Path) -> Result<()> {
+        let path = Value::from(path.to_string_lossy().into_owned());
+        self.base.function("set_active_project")?.call1(&path)?;
+        Ok(())
+    }
+
+    /// Replaces `Base.DEPOT_PATH`, which controls where packages and
+    /// precompilation caches are stored. Directories which don't exist yet
+    /// are created. Non UTF-8 paths are converted lossily.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::IOError if a directory couldn't be created, in which
+    /// case `Base.DEPOT_PATH` is left untouched.
+    pub fn set_depot_path(&mut self, paths: &[PathBuf]) -> Result<()> {
+        for path in paths {
+            fs::create_dir_all(path)?;
+        }
+
+        let depot_path = self.base.global("DEPOT_PATH")?;
+        self.base.function("empty!")?.call1(&depot_path)?;
+        let push = self.base.function("push!")?;
+        for path in paths {
+            let path = Value::from(path.to_string_lossy().into_owned());
+            push.call2(&depot_path, &path)?;
+        }
+        Ok(())
+    }
+
+    /// Activates the project at `path`, runs `f` and then restores the
+    /// previously active project. The project is restored even if `f` panics.
+    pub fn with_project<R, F>(&mut self, path: &Path, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut Julia) -> R,
+    {
+        let guard = ProjectGuard::save()?;
+        self.activate_project(path)?;
+        let ret = f(self);
+        guard.restore()?;
+        Ok(ret)
+    }
 }
 
 impl Drop for Julia {
     fn drop(&mut self) {
         if let Some(s) = self.at_exit {
-            unsafe { jl_atexit_hook(s) }
+            self.run_at_exit(s);
         }
     }
 }
@@ -1,7 +1,10 @@
 //! Module providing a wrapper for the native Julia module object.
 
-use super::{Function, IntoSymbol, JlValue, Value};
-use crate::error::Result;
+use std::convert::TryFrom;
+
+use super::{exception, Function, IntoSymbol, JlValue, Symbol, Value};
+use crate::error::{Error, Result};
+use crate::string::IntoCString;
 use crate::{jlvalues, sys::*};
 
 jlvalues! {
@@ -9,6 +12,36 @@ jlvalues! {
 }
 
 impl Module {
+    /// Returns the name of the module.
+    pub fn name(&self) -> Result<Symbol> {
+        let module = self.lock()?;
+        Symbol::new(unsafe { (*module).name })
+    }
+
+    /// Returns the module containing this module. Root modules, such as Main
+    /// and Base, are their own parent.
+    pub fn parent(&self) -> Result<Module> {
+        let module = self.lock()?;
+        Module::new(unsafe { (*module).parent })
+    }
+
+    /// Returns the fully qualified name of the module, e.g. `Base.Math`.
+    pub fn fullname(&self) -> Result<String> {
+        let mut names = vec![String::try_from(&self.name()?)?];
+        let mut module = self.lock()?;
+        loop {
+            let parent = unsafe { (*module).parent };
+            if parent.is_null() || parent == module {
+                break;
+            }
+            module = parent;
+            let name = Symbol::new(unsafe { (*module).name })?;
+            names.push(String::try_from(&name)?);
+        }
+        names.reverse();
+        Ok(names.join("."))
+    }
+
     /// Returns a global bound to the symbol `sym`.
     pub fn global<S: IntoSymbol>(&self, sym: S) -> Result<Value> {
         let module = self.lock()?;
@@ -25,6 +58,62 @@ impl Module {
             .and_then(Function::from_value)
     }
 
+    /// Returns the function bound to `name` in this module, resolving names
+    /// brought in with `using` like unqualified names in Julia code do, or
+    /// in Base if this module doesn't use it. Parent modules aren't
+    /// searched, Julia never looks there either.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UndefVar if the name isn't bound anywhere, and
+    /// Error::NotAFunction if the first binding found isn't callable.
+    pub fn function_recursive(&self, name: &str) -> Result<Function> {
+        let sym = Symbol::with_name(name)?.into_inner()?;
+        let module = self.lock()?;
+        let base = unsafe { jl_base_module };
+
+        for module in [module, base] {
+            // resolves bindings imported with `using`
+            let raw = unsafe { jl_get_global(module, sym) };
+            jl_catch!();
+            if raw.is_null() {
+                continue;
+            }
+            if unsafe { jl_isa(raw, jl_function_type as *mut _) } == 0 {
+                return Err(Error::NotAFunction);
+            }
+            return Function::new(raw);
+        }
+        Err(Error::UndefVar(name.to_owned()))
+    }
+
+    /// Evaluates every top-level statement of `src` in this module, like
+    /// `include_string`, returning the value of the last one. `name` is the
+    /// file name used in line numbers and errors.
+    ///
+    /// ## Errors
+    ///
+    /// Exceptions, including syntax errors, are returned wrapped in a
+    /// LoadError, which holds the file name and line of the failing
+    /// statement.
+    pub fn include_string(&self, src: &str, name: &str) -> Result<Value> {
+        let module = self.lock()?;
+        let name = name.into_cstring();
+        let ret = exception::isolated(|| {
+            let ret = unsafe {
+                jl_load_file_string(
+                    src.as_ptr() as *const _,
+                    src.len(),
+                    name.as_ptr() as *mut _,
+                    module,
+                )
+            };
+            jl_catch!();
+            Ok(ret)
+        })?;
+        Value::new(ret)
+    }
+
     /// Binds `value` to the symbol `sym` in this module.
     pub fn set<S: IntoSymbol>(&self, sym: S, value: &Value) -> Result<()> {
         let module = self.lock()?;
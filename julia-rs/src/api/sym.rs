@@ -1,11 +1,15 @@
 //! Module providing a wrapper for the native Julia symbol.
 
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ffi::CStr;
+use std::sync::PoisonError;
 
 use super::JlValue;
 use crate::error::{Error, Result};
-use crate::string::IntoCString;
+use crate::string::{julia_str_to_string, IntoCString};
 use crate::{jlvalues, sys::*};
 
 /// Trait implemented by every type which can be used to construct a Symbol.
@@ -17,6 +21,12 @@ jlvalues! {
     pub struct Symbol(jl_sym_t);
 }
 
+thread_local! {
+    /// Symbols looked up through `Symbol::cached`. Julia symbols are never
+    /// freed, so the wrappers stay valid for the lifetime of the runtime.
+    static CACHE: RefCell<HashMap<String, Symbol>> = RefCell::new(HashMap::new());
+}
+
 impl Symbol {
     /// Construct a new symbol with a name.
     pub fn with_name<S: IntoCString>(name: S) -> Result<Self> {
@@ -25,6 +35,19 @@ impl Symbol {
         Self::new(raw).map_err(|_| Error::InvalidSymbol)
     }
 
+    /// Construct a new symbol with a name, reusing the symbol from a
+    /// thread-local cache if it was looked up before. Useful on hot paths
+    /// which repeatedly refer to the same names.
+    pub fn cached(name: &str) -> Result<Self> {
+        if let Some(sym) = CACHE.with(|cache| cache.borrow().get(name).cloned()) {
+            return Ok(sym);
+        }
+
+        let sym = Self::with_name(name)?;
+        CACHE.with(|cache| cache.borrow_mut().insert(name.to_owned(), sym.clone()));
+        Ok(sym)
+    }
+
     // This never fails.
     /// Procedurally generates a new symbol.
     pub fn gensym() -> Self {
@@ -42,6 +65,38 @@ impl Symbol {
             Self::new_unchecked(raw)
         }
     }
+
+    /// Returns the name of the symbol without copying it.
+    fn name(&self) -> &CStr {
+        let raw = self
+            ._inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ptr();
+        unsafe { CStr::from_ptr(jl_symbol_name(raw) as *const std::ffi::c_char) }
+    }
+}
+
+// Symbols are interned, so two symbols are equal iff they are the same
+// object.
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Symbol) -> bool {
+        self.name().as_ptr() == other.name().as_ptr()
+    }
+}
+
+impl Eq for Symbol {}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Symbol) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Symbol) -> Ordering {
+        self.name().cmp(other.name())
+    }
 }
 
 impl IntoSymbol for Symbol {
@@ -61,8 +116,6 @@ impl<'a> TryFrom<&'a Symbol> for String {
     fn try_from(sym: &Symbol) -> Result<Self> {
         let raw = unsafe { jl_symbol_name(sym.lock()?) };
         jl_catch!();
-        let cstr = unsafe { CStr::from_ptr(raw as *const std::ffi::c_char) };
-        let cstring = cstr.to_owned();
-        cstring.into_string().map_err(From::from)
+        unsafe { julia_str_to_string(raw as *const std::ffi::c_char) }
     }
 }
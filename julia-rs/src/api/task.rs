@@ -1,16 +1,52 @@
 //! Module providing a wrapper for the native Julia task object.
+//!
+//! Tasks are scheduled cooperatively: a scheduled task only makes progress
+//! while the task that is currently running yields, e.g. when Rust calls
+//! `Task::wait` or `Task::fetch`, or when Julia code sleeps, waits or does IO.
 
+use super::{exception, Function, JlValue, Module, Value};
+use crate::error::Result;
 use crate::{jlvalues, sys::*};
 
 jlvalues! {
     pub struct Task(jl_task_t);
 }
 
-// impl Task {
-//     /// Construct a new Task with a Function.
-//     pub fn with_function(&self, start: &Function) -> Result<Task> {
-//         let raw = unsafe { jl_new_task(start.lock()?, 0) };
-//         jl_catch!();
-//         Task::new(raw)
-//     }
-// }
+impl Task {
+    /// Construct a new Task with a Function taking no arguments. The Task is
+    /// not scheduled.
+    pub fn with_function(start: &Function) -> Result<Self> {
+        let start = Value::new(start.lock()? as *mut jl_value_t)?;
+        let core = Module::new(unsafe { jl_core_module })?;
+        let raw = core.function("Task")?.call1(&start)?;
+        Self::from_value(raw)
+    }
+
+    /// Adds the Task to the scheduler's queue.
+    pub fn schedule(&self) -> Result<()> {
+        let task = Value::new(self.lock()? as *mut jl_value_t)?;
+        Function::base("schedule")?.call1(&task)?;
+        Ok(())
+    }
+
+    /// Checks if the Task has exited.
+    pub fn is_done(&self) -> Result<bool> {
+        let task = Value::new(self.lock()? as *mut jl_value_t)?;
+        let done = Function::base("istaskdone")?.call1(&task)?;
+        bool::try_from(&done)
+    }
+
+    /// Blocks until the Task has exited, yielding to the scheduler.
+    pub fn wait(&self) -> Result<()> {
+        let task = Value::new(self.lock()? as *mut jl_value_t)?;
+        exception::isolated(|| Function::base("wait")?.call1(&task))?;
+        Ok(())
+    }
+
+    /// Blocks until the Task has exited and returns its result. If the Task
+    /// failed, the exception is returned as an error.
+    pub fn fetch(&self) -> Result<Value> {
+        let task = Value::new(self.lock()? as *mut jl_value_t)?;
+        exception::isolated(|| Function::base("fetch")?.call1(&task))
+    }
+}
@@ -1,12 +1,19 @@
 //! Module containing traits, types and macros for interfacing with Julia
 //! values.
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::ffi::CStr;
+use std::hash::{Hash, Hasher};
+use std::ops;
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::sync::Mutex;
 
-use crate::api::{Datatype, Function, IntoSymbol};
+use crate::api::datatype::Type;
+use crate::api::{Array, ByteArray, Datatype, Function, IntoSymbol, JuliaIter, Module, Symbol};
 use crate::error::{Error, Result};
-use crate::string::{IntoCString, TryIntoString};
+use crate::string::{julia_str_to_string, IntoCString, TryIntoString};
 use crate::sys::*;
 
 /// The trait implemented by every Julia type.
@@ -71,6 +78,16 @@ where
         t.try_into_string()
     }
 
+    /// Returns the name of the type as a Symbol, without allocating a String.
+    fn typename_symbol(&self) -> Result<Symbol> {
+        let raw = self.lock()? as *mut jl_value_t;
+        let name = unsafe {
+            let dt = jl_typeof(raw) as *const jl_datatype_t;
+            (*(*dt).name).name
+        };
+        Symbol::new(name)
+    }
+
     /// Returns the type of the object as a Datatype.
     fn datatype(&self) -> Result<Datatype> {
         let raw = self.lock()? as *mut jl_value_t;
@@ -100,10 +117,16 @@ where
     }
 
     /// Sets the value of a field if it exists.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::ImmutableField if the object is immutable or the field
+    /// is const, and Error::InvalidArgument if the field is atomic, use
+    /// `set_atomic` for those.
     fn set<S: IntoSymbol>(&self, field: S, value: &Value) -> Result<()> {
         let raw = self.lock()? as *mut jl_value_t;
-        let field = field.into_symbol()?;
-        let field = field.lock()?;
+        let sym = field.into_symbol()?;
+        let field = sym.lock()?;
         let dt = self.datatype()?;
         let dt = dt.lock()?;
         let idx = unsafe { jl_field_index(dt, field, -1) };
@@ -114,12 +137,61 @@ where
         }
         let idx = idx as usize;
 
+        if unsafe { jl_field_isconst(dt, idx) } {
+            return Err(Error::ImmutableField(String::try_from(&sym)?));
+        }
+        if unsafe { jl_field_isatomic(dt, idx) } {
+            return Err(Error::InvalidArgument(format!(
+                "field {} is atomic",
+                String::try_from(&sym)?
+            )));
+        }
+
         let value = value.lock()?;
         unsafe { jl_set_nth_field(raw, idx, value) };
         jl_catch!();
         Ok(())
     }
 
+    /// Sets the value of an `@atomic` field with sequentially consistent
+    /// ordering, like `@atomic x.field = value`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::ImmutableField if the field is const and
+    /// Error::InvalidArgument if the field isn't atomic, use `set` for
+    /// those.
+    fn set_atomic<S: IntoSymbol>(&self, field: S, value: &Value) -> Result<()> {
+        let sym = field.into_symbol()?;
+        let dt = self.datatype()?;
+        let dt = dt.lock()?;
+        let idx = unsafe { jl_field_index(dt, sym.lock()?, -1) };
+        jl_catch!();
+
+        if idx.is_negative() {
+            return Err(Error::InvalidSymbol);
+        }
+        let idx = idx as usize;
+
+        if unsafe { jl_field_isconst(dt, idx) } {
+            return Err(Error::ImmutableField(String::try_from(&sym)?));
+        }
+        if !unsafe { jl_field_isatomic(dt, idx) } {
+            return Err(Error::InvalidArgument(format!(
+                "field {} isn't atomic",
+                String::try_from(&sym)?
+            )));
+        }
+
+        let this = Value::new(self.lock()? as *mut jl_value_t)?;
+        let name = Value::new(sym.lock()? as *mut jl_value_t)?;
+        let order = Value::from_value(Symbol::with_name("sequentially_consistent")?)?;
+        let core = Module::new(unsafe { jl_core_module })?;
+        core.function("setfield!")?
+            .call(&[this, name, value.clone(), order])?;
+        Ok(())
+    }
+
     /// Constructs an object of type Self from another object that implements
     /// JlValue.
     fn from_value<U, A: JlValue<U>>(val: A) -> Result<Self> {
@@ -135,9 +207,36 @@ where
     }
 }
 
+/// Checks if `v` looks like a pointer to a Julia value, i.e. it's aligned
+/// and its type tag refers to a DataType. Used to validate pointers in debug
+/// builds.
+///
+/// # Safety
+/// `v` must be non-null and readable, a dangling pointer may still crash.
+pub unsafe fn is_julia_value<T>(v: *const T) -> bool {
+    if !(v as usize).is_multiple_of(std::mem::align_of::<*const jl_value_t>()) {
+        return false;
+    }
+    let t = jl_typeof(v);
+    !t.is_null() && jl_is_datatype(t)
+}
+
+/// Creates a JlValue wrapper type. Pass `custom_debug` to implement `Debug`
+/// by hand instead of printing the whole value.
 #[macro_export]
 macro_rules! simple_jlvalue {
     ($name:ident, $type:ty) => {
+        $crate::simple_jlvalue!($name, $type, custom_debug);
+
+        impl ::std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                use $crate::api::JlValue;
+                let typename = self.typename().map_err(|_| ::std::fmt::Error)?;
+                write!(f, "{}({})", typename, self)
+            }
+        }
+    };
+    ($name:ident, $type:ty, custom_debug) => {
         #[derive(Clone)]
         pub struct $name {
             _inner: ::std::rc::Rc<::std::sync::Mutex<::std::ptr::NonNull<$type>>>,
@@ -158,6 +257,11 @@ macro_rules! simple_jlvalue {
                 if _inner.is_null() {
                     Err($crate::error::Error::NullPointer)
                 } else {
+                    debug_assert!(
+                        unsafe { $crate::api::value::is_julia_value(_inner) },
+                        "{:p} doesn't point to a Julia value",
+                        _inner
+                    );
                     unsafe {
                         Ok($name::new_unchecked(_inner))
                     }
@@ -179,14 +283,6 @@ macro_rules! simple_jlvalue {
             }
         }
 
-        impl ::std::fmt::Debug for $name {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                use $crate::api::JlValue;
-                let typename = self.typename().map_err(|_| ::std::fmt::Error)?;
-                write!(f, "{}({})", typename, self)
-            }
-        }
-
         impl ::std::fmt::Display for $name {
             fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                 use ::std::convert::TryFrom;
@@ -254,12 +350,72 @@ impl Expr {
     }
 }
 
+/// Julia function comparing two values structurally: values which are
+/// `isequal` are equal, otherwise arrays, dicts and structs of the same type
+/// are compared element by element and field by field.
+fn deep_equal_fn() -> Result<Function> {
+    super::cached_function(
+        "deep_equal",
+        r#"let
+            function deepequal(a, b)
+                isequal(a, b) && return true
+                typeof(a) === typeof(b) || return false
+                if a isa AbstractDict
+                    length(a) == length(b) || return false
+                    return all(haskey(b, k) && deepequal(v, b[k]) for (k, v) in a)
+                elseif a isa AbstractArray
+                    size(a) == size(b) || return false
+                    return all(eachindex(a)) do i
+                        isassigned(a, i) == isassigned(b, i) &&
+                            (!isassigned(a, i) || deepequal(a[i], b[i]))
+                    end
+                elseif isstructtype(typeof(a)) && nfields(a) > 0
+                    return all(1:nfields(a)) do i
+                        isdefined(a, i) == isdefined(b, i) &&
+                            (!isdefined(a, i) || deepequal(getfield(a, i), getfield(b, i)))
+                    end
+                end
+                false
+            end
+        end"#,
+    )
+}
+
 impl Value {
+    /// Construct a new Value from a pointer which is known not to be null,
+    /// skipping the check done by `JlValue::new`.
+    pub fn from_non_null(ptr: NonNull<jl_value_t>) -> Self {
+        debug_assert!(
+            unsafe { is_julia_value(ptr.as_ptr()) },
+            "{:p} doesn't point to a Julia value",
+            ptr
+        );
+        Self {
+            _inner: Rc::new(Mutex::new(ptr)),
+        }
+    }
+
     /// Nothing, Nil, Null, None.
     pub fn nothing() -> Self {
         unsafe { Self::new_unchecked(jl_nothing) }
     }
 
+    /// Returns the raw pointer to the inner jl_value, for calling sys
+    /// functions which aren't wrapped yet.
+    ///
+    /// The pointer isn't tied to the lifetime of the Value and the inner
+    /// Mutex is only held while reading it. It's the caller's responsibility
+    /// to make sure the object isn't collected while the pointer is used,
+    /// e.g. by keeping it referenced from Julia, and that the object isn't
+    /// mutated through the pointer while it's used through a Value.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::PoisonError if the inner Mutex is poisoned.
+    pub fn as_raw(&self) -> Result<*mut jl_value_t> {
+        self.lock()
+    }
+
     /// Applies function to the inner pointer.
     pub fn map<T, F>(&self, f: F) -> Result<T>
     where
@@ -268,6 +424,219 @@ impl Value {
         self.lock().map(f)
     }
 
+    /// Returns the name and value of every field, in declaration order.
+    /// Fields without names, such as those of tuples, are named by their
+    /// 1-based index. Undefined fields are returned as `nothing`.
+    pub fn fields(&self) -> Result<Vec<(String, Value)>> {
+        let raw = self.lock()?;
+        let dt = unsafe { jl_typeof(raw) as *mut jl_datatype_t };
+        let nfields = unsafe { jl_datatype_nfields(dt) };
+        let names = unsafe { jl_field_names(dt) };
+        let nnames = unsafe { jl_svec_len(names) };
+
+        let mut fields = Vec::with_capacity(nfields);
+        for i in 0..nfields {
+            let name = if i < nnames {
+                let name = unsafe { jl_svecref(names, i) };
+                if unsafe { jl_is_symbol(name) } {
+                    unsafe { julia_str_to_string(jl_symbol_name(name as *mut jl_sym_t))? }
+                } else {
+                    (i + 1).to_string()
+                }
+            } else {
+                (i + 1).to_string()
+            };
+
+            if unsafe { jl_field_isdefined(raw, i) } == 0 {
+                fields.push((name, Value::nothing()));
+                continue;
+            }
+            let field = unsafe { jl_get_nth_field(raw, i) };
+            jl_catch!();
+            fields.push((name, Value::new(field)?));
+        }
+        Ok(fields)
+    }
+
+    /// Returns the values of all fields, e.g. the elements of a tuple.
+    pub(crate) fn tuple_fields(&self) -> Result<Vec<Value>> {
+        let raw = self.lock()?;
+        let nfields = unsafe { jl_datatype_nfields(jl_typeof(raw) as *const _) };
+        let mut fields = Vec::with_capacity(nfields);
+        for i in 0..nfields {
+            let field = unsafe { jl_get_nth_field(raw, i) };
+            jl_catch!();
+            fields.push(Value::new(field)?);
+        }
+        Ok(fields)
+    }
+
+    /// Converts the values to a common type using Julia's `promote`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UnhandledException if no common type exists.
+    pub fn promote(values: &[&Value]) -> Result<Vec<Value>> {
+        if values.is_empty() {
+            return Ok(vec![]);
+        }
+        let promoted = Function::base("promote")?.call(values.iter().copied())?;
+        promoted.tuple_fields()
+    }
+
+    /// Parses `s` as a value of type `ty` using Julia's `parse`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UnhandledException containing an `Exception::Argument`
+    /// if `s` can't be parsed as `ty`.
+    pub fn parse(ty: &Datatype, s: &str) -> Result<Value> {
+        let ty = Value::new(ty.lock()? as *mut jl_value_t)?;
+        Function::base("parse")?.call2(&ty, &Value::from(s))
+    }
+
+    /// Returns the size of the Value in bytes, as reported by Julia's
+    /// `sizeof`. For arrays and strings this is the size of their data.
+    pub fn sizeof(&self) -> Result<usize> {
+        let size = Function::base("sizeof")?.call1(self)?;
+        isize::try_from(&size).map(|size| size as usize)
+    }
+
+    /// Calls the numeric predicate `name` from Base on the Value.
+    fn numeric_predicate(&self, name: &str) -> Result<bool> {
+        if !self.isa(&Datatype::number())? {
+            return Err(Error::InvalidArgument(format!(
+                "{} expects a number, got {}",
+                name,
+                self.typename()?
+            )));
+        }
+        let p = Function::base(name)?.call1(self)?;
+        bool::try_from(&p)
+    }
+
+    /// Compares two Values using Julia's `==` and `isless`, e.g. to sort a
+    /// slice of Values with `sort_by`. Values which are `==`, such as `1` and
+    /// `1.0` or `0.0` and `-0.0`, compare Equal, any other pair is ordered
+    /// by `isless`. NaN is ordered after every other number and `missing`
+    /// after everything, like Julia's `sort` does, and two NaNs compare
+    /// Equal.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::MethodError if the Values aren't comparable.
+    pub fn cmp_julia(&self, other: &Value) -> Result<Ordering> {
+        // `==` returns `missing` for missing values, which isn't equality
+        let eq = Function::base("==")?
+            .call2(self, other)
+            .map_err(Error::classify)?;
+        if eq.is_bool() && bool::try_from(&eq)? {
+            return Ok(Ordering::Equal);
+        }
+
+        let isless = Function::base("isless")?;
+        let less = isless.call2(self, other).map_err(Error::classify)?;
+        if bool::try_from(&less)? {
+            return Ok(Ordering::Less);
+        }
+        let greater = isless.call2(other, self).map_err(Error::classify)?;
+        if bool::try_from(&greater)? {
+            Ok(Ordering::Greater)
+        } else {
+            Ok(Ordering::Equal)
+        }
+    }
+
+    /// Checks if two Values are equal using Julia's `isequal`, the equality
+    /// used by `Dict` keys and `hash`. Unlike `==`, `isequal` treats NaN as
+    /// equal to itself, distinguishes `-0.0` from `0.0` and always returns a
+    /// Bool, also for `missing`.
+    ///
+    /// Note that for mutable structs without their own method, both fall
+    /// back to `===`, so two distinct but identical objects aren't equal.
+    /// Use `deep_equal` to compare those.
+    pub fn isequal(&self, other: &Value) -> Result<bool> {
+        let p = Function::base("isequal")?.call2(self, other)?;
+        bool::try_from(&p)
+    }
+
+    /// Compares two Values structurally, e.g. to assert on nested Julia
+    /// results in tests. Values which are `isequal` are equal, otherwise
+    /// arrays and dicts are compared element by element and structs of the
+    /// same type field by field, recursively.
+    ///
+    /// Cyclic structures which aren't `isequal` are not supported.
+    pub fn deep_equal(&self, other: &Value) -> Result<bool> {
+        let p = deep_equal_fn()?.call2(self, other)?;
+        bool::try_from(&p)
+    }
+
+    /// Serializes the Value using the `Serialization` standard library, to
+    /// be read back with `Julia::deserialize`.
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        let serialization = super::require("Serialization")?;
+        let buf = Function::base("IOBuffer")?.call0()?;
+        serialization.function("serialize")?.call2(&buf, self)?;
+        let bytes = Function::base("take!")?.call1(&buf)?;
+        ByteArray::from_value(bytes)?.as_vec()
+    }
+
+    /// Checks if the Value is a NaN, using Julia's `isnan`. For complex
+    /// numbers either part may be NaN.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the Value isn't a `Number`.
+    pub fn is_nan(&self) -> Result<bool> {
+        self.numeric_predicate("isnan")
+    }
+
+    /// Checks if the Value is infinite, using Julia's `isinf`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the Value isn't a `Number`.
+    pub fn is_inf(&self) -> Result<bool> {
+        self.numeric_predicate("isinf")
+    }
+
+    /// Checks if the Value is finite, using Julia's `isfinite`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidArgument if the Value isn't a `Number`.
+    pub fn is_finite(&self) -> Result<bool> {
+        self.numeric_predicate("isfinite")
+    }
+
+    /// Creates a `Base.RefValue` typed after `v`, like `Ref(v)`.
+    pub fn new_ref(v: &Value) -> Result<Value> {
+        Function::base("Ref")?.call1(v)
+    }
+
+    /// Creates an untyped `Base.RefValue{Any}` containing `v`, which can be
+    /// assigned values of any type.
+    pub fn new_ref_any(v: &Value) -> Result<Value> {
+        let base = Module::new(unsafe { jl_base_module })?;
+        let reftype = Type::from_value(base.global("Ref")?)?;
+        let reftype = reftype.apply_type1(&Value::from_value(Datatype::any())?)?;
+        Function::from_value(reftype)?.call1(v)
+    }
+
+    /// Returns the value inside a `Ref`, i.e. `ref[]`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::InvalidUnbox if the Value isn't a `Ref`.
+    pub fn deref_ref(&self) -> Result<Value> {
+        let reftype = Module::new(unsafe { jl_base_module })?.global("Ref")?;
+        let is_ref = unsafe { jl_isa(self.lock()?, reftype.lock()?) != 0 };
+        if !is_ref {
+            return Err(Error::InvalidUnbox);
+        }
+        Function::base("getindex")?.call1(self)
+    }
+
     /// Applies function to the inner pointer and returns a default value if
     /// its poisoned.
     pub fn map_or<T, F>(&self, f: F, optb: T) -> T
@@ -292,12 +661,131 @@ impl Value {
         !self._inner.is_poisoned()
     }
 
+    /// Returns an iterator over the Value using Julia's iteration protocol.
+    pub fn iterate(&self) -> Result<JuliaIter> {
+        JuliaIter::new(self)
+    }
+
+    /// Returns which kind of type the Value is: `"DataType"`, `"UnionAll"`,
+    /// `"Union"` or `"TypeofBottom"`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::NotAType if the Value isn't a type.
+    pub fn kind_name(&self) -> Result<&'static str> {
+        let raw = self.lock()?;
+        unsafe {
+            if jl_is_datatype(raw) {
+                Ok("DataType")
+            } else if jl_is_unionall(raw) {
+                Ok("UnionAll")
+            } else if jl_is_uniontype(raw) {
+                Ok("Union")
+            } else if raw == jl_bottom_type {
+                Ok("TypeofBottom")
+            } else {
+                Err(Error::NotAType)
+            }
+        }
+    }
+
     /// Checks if the Value is of a concrete Datatype.
     pub fn isa(&self, other: &Datatype) -> Result<bool> {
         let p = unsafe { jl_isa(self.lock()?, other.lock()? as *mut _) != 0 };
         Ok(p)
     }
 
+    /// Asserts that the Value is of type `ty` using `Core.typeassert`,
+    /// returning it unchanged if it is.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::TypeError if the Value isn't of type `ty`.
+    pub fn typeassert(&self, ty: &Datatype) -> Result<Value> {
+        let ty = Value::new(ty.lock()? as *mut jl_value_t)?;
+        let core = Module::new(unsafe { jl_core_module })?;
+        core.function("typeassert")?
+            .call2(self, &ty)
+            .map_err(Error::classify)
+    }
+
+    /// Unboxes the Value into the matching RustValue variant, based on its
+    /// type tag. Signed integers and floats are widened to `i64` and `f64`,
+    /// any other Value is returned as `RustValue::Other`.
+    pub fn unbox_any(&self) -> Result<RustValue> {
+        let raw = self.lock()?;
+        let unboxed = unsafe {
+            if jl_is_int64(raw) {
+                RustValue::I64(jl_unbox_int64(raw))
+            } else if jl_is_int32(raw) {
+                RustValue::I64(jl_unbox_int32(raw) as i64)
+            } else if jl_is_int16(raw) {
+                RustValue::I64(jl_unbox_int16(raw) as i64)
+            } else if jl_is_int8(raw) {
+                RustValue::I64(jl_unbox_int8(raw) as i64)
+            } else if jl_is_float64(raw) {
+                RustValue::F64(jl_unbox_float64(raw))
+            } else if jl_is_float32(raw) {
+                RustValue::F64(jl_unbox_float32(raw) as f64)
+            } else if jl_is_bool(raw) {
+                RustValue::Bool(jl_unbox_bool(raw) != 0)
+            } else if jl_is_string(raw) {
+                RustValue::Str(String::try_from(self)?)
+            } else {
+                RustValue::Other(self.clone())
+            }
+        };
+        Ok(unboxed)
+    }
+
+    /// Checks if the Value can be called, i.e. if it's a function with at
+    /// least one method, a builtin, an intrinsic, or any other object with
+    /// a call overload, such as a type with constructors or a callable
+    /// struct. Returns false if the check itself fails.
+    pub fn is_callable(&self) -> bool {
+        let raw = match self.lock() {
+            Ok(raw) => raw,
+            Err(_) => return false,
+        };
+        if unsafe { jl_is_intrinsic(raw) || jl_isa(raw, jl_builtin_type as *mut _) != 0 } {
+            return true;
+        }
+        let has_methods = || -> Result<bool> {
+            let methods = Function::base("methods")?.call1(self)?;
+            let empty = Function::base("isempty")?.call1(&methods)?;
+            Ok(!bool::try_from(&empty)?)
+        };
+        has_methods().unwrap_or(false)
+    }
+
+    /// Returns the value at a dotted path of field names, e.g. `a.b.c`,
+    /// getting one field at a time.
+    ///
+    /// ## Errors
+    ///
+    /// Returns Error::UndefVar with the path up to and including the first
+    /// component which isn't a field of the value reached so far, e.g.
+    /// `a.b` if `b` is missing.
+    pub fn get_path(&self, path: &str) -> Result<Value> {
+        let mut value = self.clone();
+        let mut end = 0;
+        for field in path.split('.') {
+            end += field.len();
+            value = match value.get(field) {
+                Ok(value) => value,
+                Err(Error::InvalidSymbol) => return Err(Error::UndefVar(path[..end].to_owned())),
+                Err(err) => return Err(err),
+            };
+            end += 1;
+        }
+        Ok(value)
+    }
+
+    /// Converts the Value into any type implementing FromJulia.
+    pub fn extract<T: FromJulia>(&self) -> Result<T> {
+        T::from_julia(self)
+    }
+
     /// Checks if the types of two Values are equal.
     pub fn types_equal(&self, other: &Self) -> Result<bool> {
         let p = unsafe { jl_types_equal(self.lock()?, other.lock()?) != 0 };
@@ -544,6 +1032,79 @@ impl Default for Value {
     }
 }
 
+/// A Value unboxed into a Rust type by `Value::unbox_any`.
+#[derive(Debug, Clone)]
+pub enum RustValue {
+    /// A signed integer.
+    I64(i64),
+    /// A floating point number.
+    F64(f64),
+    /// A Bool.
+    Bool(bool),
+    /// A String.
+    Str(String),
+    /// Any other Value, left as is.
+    Other(Value),
+}
+
+/// Wrapper comparing and hashing a Value by identity, like Julia's `===` and
+/// `objectid`, so it can be used as a key in a `HashMap` or `HashSet`.
+///
+/// Mutable objects are only equal to themselves, while immutable values,
+/// such as numbers, strings and tuples, are equal if their contents are.
+/// Note that `1 === 1.0` is false, unlike `1 == 1.0`.
+#[derive(Debug, Clone)]
+pub struct ValueById(pub Value);
+
+impl PartialEq for ValueById {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = match (self.0.lock(), other.0.lock()) {
+            (Ok(a), Ok(b)) => (a, b),
+            _ => return false,
+        };
+        if a == b {
+            return true;
+        }
+        let egal = Module::new(unsafe { jl_core_module })
+            .and_then(|core| core.function("==="))
+            .and_then(|f| f.call2(&self.0, &other.0));
+        match egal {
+            Ok(egal) => bool::try_from(&egal).unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Eq for ValueById {}
+
+impl Hash for ValueById {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if let Ok(raw) = self.0.lock() {
+            unsafe { jl_object_id(raw) }.hash(state);
+        }
+    }
+}
+
+/// Implements an arithmetic operator for `&Value` by calling the Julia
+/// function of the same name. The result is a `Result<Value>`, any exception
+/// thrown by Julia (e.g. a MethodError for incompatible types) is returned as
+/// an error.
+macro_rules! value_op {
+    ($trait:ident, $method:ident, $name:expr) => {
+        impl ops::$trait<&Value> for &Value {
+            type Output = Result<Value>;
+            fn $method(self, rhs: &Value) -> Result<Value> {
+                Function::base($name)?.call2(self, rhs)
+            }
+        }
+    };
+}
+
+value_op!(Add, add, "+");
+value_op!(Sub, sub, "-");
+value_op!(Mul, mul, "*");
+value_op!(Div, div, "/");
+
 macro_rules! box_simple {
     ($t1:ident => $t2:ident) => {
         box_simple!($t1 => $t2, |val| { val } );
@@ -652,10 +1213,104 @@ impl<'a> TryFrom<&'a Value> for String {
             let raw = unsafe { jl_string_ptr(val) };
             jl_catch!();
 
-            let cstr = unsafe { CStr::from_ptr(raw) };
-            cstr.to_owned().into_string().map_err(From::from)
+            unsafe { julia_str_to_string(raw) }
         } else {
             Err(Error::InvalidUnbox)
         }
     }
 }
+
+impl TryFrom<&Value> for Value {
+    type Error = Error;
+    fn try_from(val: &Value) -> Result<Self> {
+        Ok(val.clone())
+    }
+}
+
+/// Conversion from a Julia Value into a Rust type, used by `Value::extract`.
+/// Implemented for the primitive types, String, Value and Vec, and meant to
+/// be implemented for user types, e.g. by extracting every field with
+/// `JlValue::get`.
+///
+/// ## Contract
+///
+/// `value` is only guaranteed to be rooted by the caller for the duration
+/// of the call. Implementations should copy what they need into owned Rust
+/// data and not keep raw pointers to it, or to any Julia object created
+/// during the conversion, after returning.
+///
+/// Exceptions thrown by Julia while converting have to be caught, e.g.
+/// with `jl_catch!` after every FFI call, and returned as errors.
+/// Implementations must not panic, as unwinding into Julia is undefined
+/// behaviour when the conversion runs inside a callback.
+pub trait FromJulia: Sized {
+    /// Converts `value`, returning Error::InvalidUnbox if it has the wrong
+    /// type.
+    fn from_julia(value: &Value) -> Result<Self>;
+}
+
+macro_rules! from_julia_try_from {
+    ($($t:ty),*) => {
+        $(
+            impl FromJulia for $t {
+                fn from_julia(value: &Value) -> Result<Self> {
+                    Self::try_from(value)
+                }
+            }
+        )*
+    };
+}
+
+from_julia_try_from!(bool, char, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+from_julia_try_from!(String, Value);
+
+/// Converts every element of a Julia Array, in linear order.
+impl<T: FromJulia> FromJulia for Vec<T> {
+    fn from_julia(value: &Value) -> Result<Self> {
+        if !value.is_array() {
+            return Err(Error::InvalidUnbox);
+        }
+
+        let raw = value.lock()? as *mut jl_array_t;
+        let len = unsafe { jl_array_len(raw) };
+        let mut vec = Vec::with_capacity(len);
+        for i in 0..len {
+            // boxes bits elements, throws for undefined ones
+            let elem = unsafe { jl_arrayref(raw, i) };
+            jl_catch!();
+            vec.push(T::from_julia(&Value::new(elem)?)?);
+        }
+        Ok(vec)
+    }
+}
+
+/// Collects a Julia `AbstractDict` into a HashMap, converting every key and
+/// value with the appropriate `TryFrom`.
+impl<K, V> TryFrom<&Value> for HashMap<K, V>
+where
+    K: for<'a> TryFrom<&'a Value, Error = Error> + Eq + Hash,
+    V: for<'a> TryFrom<&'a Value, Error = Error>,
+{
+    type Error = Error;
+    fn try_from(val: &Value) -> Result<Self> {
+        let dict = Module::new(unsafe { jl_base_module })?.global("AbstractDict")?;
+        let is_dict = unsafe { jl_isa(val.lock()?, dict.lock()?) != 0 };
+        if !is_dict {
+            return Err(Error::InvalidUnbox);
+        }
+
+        // collect into a Vector{Any}, so that the elements are always boxed
+        let any = Value::new(unsafe { jl_any_type } as *mut jl_value_t)?;
+        let collect = Function::base("collect")?;
+        let keys = collect.call2(&any, &Function::base("keys")?.call1(val)?)?;
+        let values = collect.call2(&any, &Function::base("values")?.call1(val)?)?;
+        let keys = Array::from_value(keys)?.as_vec()?;
+        let values = Array::from_value(values)?.as_vec()?;
+
+        let mut map = HashMap::with_capacity(keys.len());
+        for (key, value) in keys.iter().zip(values.iter()) {
+            map.insert(K::try_from(key)?, V::try_from(value)?);
+        }
+        Ok(map)
+    }
+}
@@ -22,14 +22,31 @@ pub type Result<T> = result::Result<T, Error>;
 pub enum Error {
     /// An exception has occurred.
     UnhandledException(Exception),
+    /// A BoundsError has occurred. Contains the exception's message.
+    BoundsError(String),
+    /// A MethodError has occurred. Contains the exception's message.
+    MethodError(String),
+    /// A TypeError has occurred. Contains the exception's message.
+    TypeError(String),
     /// Cannot unbox into a certain type.
     InvalidUnbox,
+    /// A name wasn't defined. Contains the name, or the dotted path up to
+    /// it.
+    UndefVar(String),
     /// Tried to call a non-function object.
     NotAFunction,
+    /// Tried to use a non-type object as a type.
+    NotAType,
     /// An error occurred while trying to call a function.
     CallError,
     /// An error occurred while evaluating a string or expression.
     EvalError,
+    /// A string couldn't be parsed. Contains Julia's message, which points
+    /// at the offending line and column, followed by the source if it's
+    /// short.
+    ParseError(String),
+    /// A call didn't finish in time and was interrupted.
+    Timeout,
     /// Attempt to construct a string or Julia object with a null pointer.
     NullPointer,
     /// Invalid characters used in symbol. See
@@ -38,6 +55,10 @@ pub enum Error {
     InvalidSymbol,
     /// Attempt to initialize Julia in a thread where it's already initialized.
     JuliaInitialized,
+    /// Attempt to set a field of an immutable struct or a const field.
+    ImmutableField(String),
+    /// An argument passed to a julia-rs function was invalid.
+    InvalidArgument(String),
     /// Wrapper for ffi::FromBytesWithNulError.
     CStrError(FromBytesWithNulError),
     /// Wrapper for ffi::NulError.
@@ -61,16 +82,25 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Self::UnhandledException(ref ex) => write!(f, "UnhandledException({})", ex),
+            Self::BoundsError(ref msg) => write!(f, "BoundsError({})", msg),
+            Self::MethodError(ref msg) => write!(f, "MethodError({})", msg),
+            Self::TypeError(ref msg) => write!(f, "TypeError({})", msg),
             Self::CStrError(ref err) => write!(f, "CStrError({})", err),
             Self::CStringError(ref err) => write!(f, "CStringError({})", err),
             Self::UTF8Error(ref err) => write!(f, "UTF8Error({})", err),
             Self::FromUTF8Error(ref err) => write!(f, "FromUTF8Error({})", err),
             Self::IntoStringError(ref err) => write!(f, "IntoStringError({})", err),
             Self::IOError(ref err) => write!(f, "IOError({})", err),
+            Self::ImmutableField(ref name) => write!(f, "ImmutableField({})", name),
+            Self::InvalidArgument(ref msg) => write!(f, "InvalidArgument({})", msg),
+            Self::ParseError(ref msg) => write!(f, "ParseError({})", msg),
+            Self::UndefVar(ref name) => write!(f, "UndefVar({})", name),
             Self::InvalidUnbox
             | Self::NotAFunction
+            | Self::NotAType
             | Self::CallError
             | Self::EvalError
+            | Self::Timeout
             | Self::NullPointer
             | Self::InvalidSymbol
             | Self::JuliaInitialized
@@ -80,6 +110,42 @@ impl fmt::Display for Error {
     }
 }
 
+impl Error {
+    /// Maps well-known exceptions to their dedicated variants, carrying the
+    /// exception's message. Any other error is returned unchanged, as is an
+    /// exception whose message couldn't be retrieved.
+    pub fn classify(self) -> Self {
+        let ex = match self {
+            Self::UnhandledException(ex) => ex,
+            err => return err,
+        };
+        let variant: fn(String) -> Self = match ex {
+            Exception::Bounds(_) => Self::BoundsError,
+            Exception::Method(_) => Self::MethodError,
+            Exception::Type(_) => Self::TypeError,
+            _ => return Self::UnhandledException(ex),
+        };
+        match ex.message() {
+            Ok(msg) => variant(msg),
+            Err(_) => Self::UnhandledException(ex),
+        }
+    }
+
+    /// Formats the error for end users. Exceptions are formatted as Julia's
+    /// `showerror` prints them, classified exceptions and parse errors as
+    /// the message they carry, any other error with `Display`.
+    pub fn user_message(&self) -> String {
+        match *self {
+            Self::UnhandledException(ref ex) => ex.to_string(),
+            Self::BoundsError(ref msg)
+            | Self::MethodError(ref msg)
+            | Self::TypeError(ref msg)
+            | Self::ParseError(ref msg) => msg.clone(),
+            ref err => err.to_string(),
+        }
+    }
+}
+
 impl From<FromBytesWithNulError> for Error {
     fn from(err: FromBytesWithNulError) -> Self {
         Self::CStrError(err)
@@ -107,7 +107,7 @@ macro_rules! extern_jl {
                 $(
                     decl.push_str(self.$name);
                 )*
-                jl.load(&mut decl.as_bytes(), Some(concat!( "jl-decl-", stringify!($lib), ".jl" )))
+                jl.load_file(&mut decl.as_bytes(), Some(concat!( "jl-decl-", stringify!($lib), ".jl" )))
             }
         }
     }
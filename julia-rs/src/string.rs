@@ -20,6 +20,20 @@ macro_rules! cstr {
     }
 }
 
+/// Copies a nul-terminated string obtained from Julia into a Rust String.
+///
+/// # Safety
+/// `ptr` must be null or point to a nul-terminated string which stays valid
+/// for the duration of the call.
+///
+/// ## Errors
+///
+/// Returns Error::NullPointer if `ptr` is null and Error::IntoStringError if
+/// the string isn't valid UTF-8.
+pub unsafe fn julia_str_to_string(ptr: *const c_char) -> Result<String, Error> {
+    ptr.try_into_string()
+}
+
 /// Trait for types which can be converted into a CString.
 pub trait IntoCString {
     fn into_cstring(self) -> CString;
@@ -102,3 +116,42 @@ impl TryIntoString for String {
         Ok(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{c_char, CStr, CString};
+    use std::ptr;
+
+    use super::{julia_str_to_string, IntoCString, TryIntoString};
+    use crate::error::Error;
+
+    #[test]
+    fn round_trip() {
+        for s in ["", "julia", "ünïcödé", "λ -> λ"] {
+            assert_eq!(s.into_cstring().try_into_string().unwrap(), s);
+            assert_eq!(s.to_owned().into_cstring().try_into_string().unwrap(), s);
+            assert_eq!((&s.to_owned()).into_cstring().try_into_string().unwrap(), s);
+
+            let cstring = s.into_cstring();
+            let cstr: &CStr = &cstring;
+            assert_eq!(cstr.into_cstring().try_into_string().unwrap(), s);
+            assert_eq!(cstr.try_into_string().unwrap(), s);
+
+            let converted = unsafe { julia_str_to_string(cstring.as_ptr()) };
+            assert_eq!(converted.unwrap(), s);
+        }
+    }
+
+    #[test]
+    fn null_pointer() {
+        let converted = unsafe { julia_str_to_string(ptr::null::<c_char>()) };
+        assert!(matches!(converted, Err(Error::NullPointer)));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let cstring = CString::new(vec![0xff, 0xfe]).unwrap();
+        let converted = unsafe { julia_str_to_string(cstring.as_ptr()) };
+        assert!(matches!(converted, Err(Error::IntoStringError(_))));
+    }
+}
@@ -13,6 +13,11 @@ mod threads;
 pub use atomics::*;
 pub use threads::*;
 
+// Exported by libjulia but only declared in internal headers.
+extern "C" {
+    pub fn jl_enable_gc_logging(enable: i32);
+}
+
 pub unsafe fn jl_astaggedvalue<T>(v: *const T) -> *const jl_taggedvalue_t {
     v.byte_sub(size_of::<jl_taggedvalue_t>()) as *const jl_taggedvalue_t
 }
@@ -48,8 +53,7 @@ pub unsafe fn jl_array_ndimwords(ndims: usize) -> usize {
 
 pub unsafe fn jl_to_typeof(t: usize) -> *const jl_value_t {
     if t < ((jl_small_typeof_tags_jl_max_tags << 4) as usize) {
-        return jl_small_typeof[t / (jl_small_typeof.len() * size_of::<*const jl_datatype_t>())]
-            as *const jl_value_t;
+        return jl_small_typeof[t / size_of::<*const jl_datatype_t>()] as *const jl_value_t;
     }
     t as *const jl_value_t
 }
@@ -484,7 +488,7 @@ pub unsafe fn jl_ptr_offset(st: *mut jl_datatype_t, i: isize) -> u32 {
 pub unsafe fn jl_field_isatomic(st: *mut jl_datatype_t, i: usize) -> bool {
     let atomicfields = (*(*st).name).atomicfields;
     if !atomicfields.is_null() {
-        if (atomicfields.add(i / 32) as usize) & (1 << (i % 32)) != 0 {
+        if *atomicfields.add(i / 32) & (1 << (i % 32)) != 0 {
             return true;
         }
     }
@@ -498,7 +502,7 @@ pub unsafe fn jl_field_isconst(st: *mut jl_datatype_t, i: usize) -> bool {
     }
     let constfields = (*tn).constfields;
     if !constfields.is_null() {
-        if (constfields.add(i / 32) as usize) & (1 << (i % 32)) != 0 {
+        if *constfields.add(i / 32) & (1 << (i % 32)) != 0 {
             return true;
         }
     }